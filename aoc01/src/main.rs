@@ -1,48 +1,61 @@
-use std::fs;
+mod report;
+mod sonar;
 
-fn main() {
-    let input_file_contents = fs::read_to_string("inputs/input-01.txt").unwrap();
-
-    let values: Vec<i32> = input_file_contents
-        .trim()
-        .split("\r\n")
-        .map(|value| value.parse::<i32>().unwrap())
-        .collect();
+use std::{env, io};
+use std::fs::File;
+use std::io::BufReader;
+use sonar::DepthAnalyzer;
 
-    let answer_1_1 = count_measurements_larger_than_previous(&values);
-    let answer_1_2 = count_measurement_windows_larger_than_previous(&values, 3);
+fn main() {
+    let input_file = BufReader::new(File::open("inputs/input-01.txt").unwrap());
+
+    // the depths are only kept in memory when the report needs them
+    match env::args().any(|arg| arg == "--report") {
+        true => {
+            let depths: Vec<i64> = sonar::read_depths(input_file)
+                .map(|depth| depth.unwrap())
+                .collect();
+
+            let mut analyzer = DepthAnalyzer::new(&[1, 3]);
+            analyzer.extend(depths.iter().copied());
+
+            print_answers(&analyzer);
+            print_depth_report(&depths);
+        },
+        false => print_answers(&sonar::analyze(input_file, &[1, 3]).unwrap())
+    }
+}
 
-    println!("answer 1.1: {}", answer_1_1);
-    println!("answer 1.2: {}", answer_1_2);
+fn print_answers(analyzer: &DepthAnalyzer) {
+    println!("answer 1.1: {}", analyzer.get_increases(1).unwrap());
+    println!("answer 1.2: {}", analyzer.get_increases(3).unwrap());
 }
 
-fn count_measurements_larger_than_previous(values: &Vec<i32>) -> i32 {
-    let mut previous = i32::MAX;
-    let mut measurements_larger_than_previous = 0;
+/// prints a summary of the sweep, followed by the 3-measurement window sums as CSV
+fn print_depth_report(depths: &[i64]) {
+    let report = report::create_depth_report(depths, 3);
 
-    for value in values {
-        if *value > previous {
-            measurements_larger_than_previous += 1;
-        }
-        previous = *value;
-    }
+    println!("measurements: {}", report.measurements);
+    println!("longest increasing run: {} measurements from index {}", report.longest_increasing_run.length, report.longest_increasing_run.start);
 
-    measurements_larger_than_previous
-}
+    if let Some(drop) = &report.largest_drop {
+        println!("largest drop: {} at index {}", drop.amount, drop.index);
+    }
 
-fn count_measurement_windows_larger_than_previous(values: &Vec<i32>, window_size: usize) -> i32 {
-    let mut measurement_windows = vec![];
+    println!("local minima: {:?}", report.local_minima);
+    println!("local maxima: {:?}", report.local_maxima);
+    println!();
 
-    for i in 0..values.len() {
-        let value = values[i];
-        measurement_windows.push(value);
+    report.write_windows_csv(io::stdout()).unwrap();
+}
 
-        for j in 1..window_size {
-            if j > i { break; }
+#[test]
+fn test_sample_input() {
+    let sample_input = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
 
-            measurement_windows[i - j] += value;
-        }
-    }
+    let mut analyzer = DepthAnalyzer::new(&[1, 3]);
+    analyzer.extend(sample_input);
 
-    count_measurements_larger_than_previous(&measurement_windows)
+    assert_eq!(analyzer.get_increases(1), Some(7));
+    assert_eq!(analyzer.get_increases(3), Some(5));
 }
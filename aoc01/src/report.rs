@@ -0,0 +1,189 @@
+use std::collections::VecDeque;
+use std::io::{self, Write};
+
+/// a stretch of consecutive measurements, each deeper than the one before
+#[derive(Eq, PartialEq, Debug)]
+pub struct Run {
+    pub start: usize,
+    pub length: usize
+}
+
+/// the biggest decrease between two neighbouring measurements, index being the shallower one
+#[derive(Eq, PartialEq, Debug)]
+pub struct DepthDrop {
+    pub index: usize,
+    pub amount: u64
+}
+
+/// depths[start..start + window_size] summarized
+#[derive(PartialEq, Debug)]
+pub struct WindowStats {
+    pub start: usize,
+    pub sum: i128,
+    pub min: i64,
+    pub max: i64,
+    pub mean: f64
+}
+
+#[derive(PartialEq, Debug)]
+pub struct DepthReport {
+    pub measurements: usize,
+    pub window_size: usize,
+    pub longest_increasing_run: Run,
+    pub largest_drop: Option<DepthDrop>,
+    pub local_minima: Vec<usize>,
+    pub local_maxima: Vec<usize>,
+    pub windows: Vec<WindowStats>
+}
+
+impl DepthReport {
+    /// writes one row per window: index,sum,min,max,mean
+    pub fn write_windows_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "index,sum,min,max,mean")?;
+
+        for WindowStats { start, sum, min, max, mean } in self.windows.iter() {
+            writeln!(writer, "{},{},{},{},{}", start, sum, min, max, mean)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// describes the shape of the sweep rather than only counting increases
+///
+/// local extrema are measurements strictly deeper (or shallower) than both neighbours,
+/// so the first and last measurements and flat stretches are never included
+pub fn create_depth_report(depths: &[i64], window_size: usize) -> DepthReport {
+    if window_size == 0 {
+        panic!("window size must be at least 1");
+    }
+
+    let mut longest_increasing_run = Run { start: 0, length: depths.len().min(1) };
+    let mut run_start = 0;
+    let mut largest_drop: Option<DepthDrop> = None;
+    let mut local_minima = vec![];
+    let mut local_maxima = vec![];
+
+    for i in 1..depths.len() {
+        let (previous, depth) = (depths[i - 1], depths[i]);
+
+        if depth <= previous {
+            run_start = i;
+        }
+
+        if i + 1 - run_start > longest_increasing_run.length {
+            longest_increasing_run = Run { start: run_start, length: i + 1 - run_start };
+        }
+
+        if depth < previous {
+            let amount = previous.abs_diff(depth);
+
+            if largest_drop.as_ref().is_none_or(|drop| amount > drop.amount) {
+                largest_drop = Some(DepthDrop { index: i, amount });
+            }
+        }
+
+        if i + 1 < depths.len() {
+            let next = depths[i + 1];
+
+            if depth < previous && depth < next {
+                local_minima.push(i);
+            } else if depth > previous && depth > next {
+                local_maxima.push(i);
+            }
+        }
+    }
+
+    DepthReport {
+        measurements: depths.len(),
+        window_size,
+        longest_increasing_run,
+        largest_drop,
+        local_minima,
+        local_maxima,
+        windows: calculate_window_stats(depths, window_size)
+    }
+}
+
+/// keeps a monotonic queue of indexes for both the min and the max, so every window is O(1) amortized
+fn calculate_window_stats(depths: &[i64], window_size: usize) -> Vec<WindowStats> {
+    let mut windows = vec![];
+    let mut min_queue: VecDeque<usize> = VecDeque::new();
+    let mut max_queue: VecDeque<usize> = VecDeque::new();
+    let mut sum: i128 = 0;
+
+    for (i, depth) in depths.iter().enumerate() {
+        sum += *depth as i128;
+
+        while min_queue.back().is_some_and(|j| depths[*j] >= *depth) {
+            min_queue.pop_back();
+        }
+        while max_queue.back().is_some_and(|j| depths[*j] <= *depth) {
+            max_queue.pop_back();
+        }
+        min_queue.push_back(i);
+        max_queue.push_back(i);
+
+        if i + 1 < window_size {
+            continue;
+        }
+
+        let start = i + 1 - window_size;
+
+        if start > 0 {
+            sum -= depths[start - 1] as i128;
+        }
+        if *min_queue.front().unwrap() < start {
+            min_queue.pop_front();
+        }
+        if *max_queue.front().unwrap() < start {
+            max_queue.pop_front();
+        }
+
+        windows.push(WindowStats {
+            start,
+            sum,
+            min: depths[*min_queue.front().unwrap()],
+            max: depths[*max_queue.front().unwrap()],
+            mean: sum as f64 / window_size as f64
+        });
+    }
+
+    windows
+}
+
+#[test]
+fn test_sample_input() {
+    let sample_input = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+    let report = create_depth_report(&sample_input, 3);
+
+    assert_eq!(report.longest_increasing_run, Run { start: 0, length: 4 });
+    assert_eq!(report.largest_drop, Some(DepthDrop { index: 4, amount: 10 }));
+    assert_eq!(report.local_minima, vec![4, 8]);
+    assert_eq!(report.local_maxima, vec![3, 7]);
+
+    let sums: Vec<i128> = report.windows.iter().map(|window| window.sum).collect();
+    assert_eq!(sums, vec![607, 618, 618, 617, 647, 716, 769, 792]);
+
+    assert_eq!(report.windows[3], WindowStats { start: 3, sum: 617, min: 200, max: 210, mean: 617.0 / 3.0 });
+    assert_eq!(report.windows[7], WindowStats { start: 7, sum: 792, min: 260, max: 269, mean: 264.0 });
+}
+
+#[test]
+fn test_windows_csv() {
+    let report = create_depth_report(&[1, 4, 2, i64::MAX], 2);
+    let mut csv = vec![];
+    report.write_windows_csv(&mut csv).unwrap();
+
+    assert_eq!(String::from_utf8(csv).unwrap(), format!("index,sum,min,max,mean\n0,5,1,4,2.5\n1,6,2,4,3\n2,{},2,{},{}\n",
+        i64::MAX as i128 + 2, i64::MAX, (i64::MAX as i128 + 2) as f64 / 2.0));
+}
+
+#[test]
+fn test_short_input() {
+    let report = create_depth_report(&[5], 3);
+
+    assert_eq!(report.longest_increasing_run, Run { start: 0, length: 1 });
+    assert_eq!(report.largest_drop, None);
+    assert!(report.windows.is_empty());
+}
@@ -0,0 +1,121 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+
+/// counts how often the sum of a sliding window of depths increases, for several window sizes at once
+///
+/// two neighbouring windows of size n share all but one depth on each end, so
+///
+///     sum(depths[i+1..=i+n]) > sum(depths[i..i+n])  <=>  depths[i+n] > depths[i]
+///
+/// meaning only the last n depths need to be kept, and window sums are never computed (or overflowed)
+pub struct DepthAnalyzer {
+    window_sizes: Vec<usize>,
+    increases: Vec<u64>,
+    history: VecDeque<i64>,
+    max_window_size: usize
+}
+
+impl DepthAnalyzer {
+    pub fn new(window_sizes: &[usize]) -> DepthAnalyzer {
+        if window_sizes.contains(&0) {
+            panic!("window size must be at least 1");
+        }
+
+        let max_window_size = window_sizes.iter().copied().max().unwrap_or(0);
+
+        DepthAnalyzer {
+            window_sizes: window_sizes.to_vec(),
+            increases: vec![0; window_sizes.len()],
+            history: VecDeque::with_capacity(max_window_size + 1),
+            max_window_size
+        }
+    }
+
+    pub fn push(&mut self, depth: i64) {
+        for (i, window_size) in self.window_sizes.iter().enumerate() {
+            if self.history.len() < *window_size {
+                continue;
+            }
+
+            let leaving_depth = self.history[self.history.len() - window_size];
+
+            if depth > leaving_depth {
+                self.increases[i] += 1;
+            }
+        }
+
+        self.history.push_back(depth);
+
+        if self.history.len() > self.max_window_size {
+            self.history.pop_front();
+        }
+    }
+
+    /// number of times a window of window_size depths was larger than the window before it
+    pub fn get_increases(&self, window_size: usize) -> Option<u64> {
+        self.window_sizes.iter()
+            .position(|size| *size == window_size)
+            .map(|i| self.increases[i])
+    }
+}
+
+impl Extend<i64> for DepthAnalyzer {
+    fn extend<T: IntoIterator<Item = i64>>(&mut self, depths: T) {
+        for depth in depths {
+            self.push(depth);
+        }
+    }
+}
+
+/// reads one depth per line, skipping blank lines, without loading the whole report into memory
+pub fn read_depths<R: BufRead>(reader: R) -> impl Iterator<Item = io::Result<i64>> {
+    reader.lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(|(index, line)| {
+            let line = line?;
+
+            line.trim().parse::<i64>().map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("invalid depth {:?} on line {}", line.trim(), index + 1))
+            })
+        })
+}
+
+/// streams every depth from reader into an analyzer for the given window sizes
+pub fn analyze<R: BufRead>(reader: R, window_sizes: &[usize]) -> io::Result<DepthAnalyzer> {
+    let mut analyzer = DepthAnalyzer::new(window_sizes);
+
+    for depth in read_depths(reader) {
+        analyzer.push(depth?);
+    }
+
+    Ok(analyzer)
+}
+
+#[test]
+fn test_sample_input() {
+    let sample_input = "199\r\n200\r\n208\r\n210\r\n200\r\n207\r\n240\r\n269\r\n260\r\n263\r\n";
+    let analyzer = analyze(sample_input.as_bytes(), &[1, 3, 10, 11]).unwrap();
+
+    assert_eq!(analyzer.get_increases(1), Some(7));
+    assert_eq!(analyzer.get_increases(3), Some(5));
+    assert_eq!(analyzer.get_increases(10), Some(0));
+    assert_eq!(analyzer.get_increases(11), Some(0));
+    assert_eq!(analyzer.get_increases(2), None);
+}
+
+#[test]
+fn test_large_depths() {
+    let mut analyzer = DepthAnalyzer::new(&[2]);
+    analyzer.extend([i64::MAX, i64::MAX, i64::MAX - 1, i64::MAX]);
+
+    assert_eq!(analyzer.get_increases(2), Some(0));
+}
+
+#[test]
+fn test_invalid_depth() {
+    let error = analyze("1\n\n2\nthree\n".as_bytes(), &[1]).err().unwrap();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(error.to_string(), "invalid depth \"three\" on line 4");
+}
@@ -1,58 +1,65 @@
-use std::fs;
+mod script;
+mod submarine;
+mod trajectory;
 
-enum Direction { Forward, Down, Up }
+use std::{env, fs, io};
+use script::{Script, ScriptError};
+use submarine::{AimModel, PlainModel};
 
-struct Command {
+#[derive(Clone, Copy)]
+pub enum Direction { Forward, Down, Up }
+
+#[derive(Clone)]
+pub struct Command {
     direction: Direction,
-    units: u32
+    units: i64,
+    line: usize
+}
+
+/// the command on the given line pushed a value outside of i64, or the final product did
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum NavigationError {
+    Overflow(usize),
+    ProductOverflow
 }
 
 fn main() {
     let input_file_contents = fs::read_to_string("inputs/input-02.txt").unwrap();
 
-    let commands: Vec<Command> = input_file_contents
-        .trim()
-        .split("\r\n")
-        .map(|value| {
-            let command_string = value.split(" ").collect::<Vec<&str>>();
+    let commands = parse(input_file_contents).unwrap();
 
-            let direction = match command_string[0] {
-                "forward" => Direction::Forward,
-                "down" =>    Direction::Down,
-                "up" =>      Direction::Up,
-                _ =>         panic!("invalid direction")
-            };
+    println!("answer 2.1: {}", calculate_position(&commands).unwrap());
+    println!("answer 2.2: {}", calculate_position_with_aim(&commands).unwrap());
+
+    if env::args().any(|arg| arg == "--csv") {
+        trajectory::write_csv(&mut [("plain", &mut PlainModel), ("aim", &mut AimModel)], &commands, io::stdout().lock()).unwrap();
+    }
 
-            let units = command_string[1].parse::<u32>().unwrap();
+    // the svg needs the bounds of every path before it can draw any of them, so they're recorded first
+    if env::args().any(|arg| arg == "--svg") {
+        let paths = vec![
+            ("plain", trajectory::record(&mut PlainModel, &commands).unwrap()),
+            ("aim", trajectory::record(&mut AimModel, &commands).unwrap()),
+        ];
 
-            Command { direction, units }
-        })
-        .collect();
+        trajectory::write_svg(&paths, io::stdout()).unwrap();
+    }
+}
 
-    println!("answer 2.1: {}", calculate_position(&commands));
-    println!("answer 2.2: {}", calculate_position_with_aim(&commands));
+/// parses a submarine script (see `Script`) into the commands it expands to
+fn parse(input: String) -> Result<Vec<Command>, ScriptError> {
+    Script::parse(&input)?.expand()
 }
 
 /// position is calculated by using a series of commands
 ///
 /// Forward: increase distance by X units
 /// Down: increase depth by X units
-/// Up: decrease depth by X units
+/// Up: decrease depth by X units, possibly above the surface
 ///
 /// Returns distance * depth
-fn calculate_position(commands: &Vec<Command>) -> u32 {
-    let mut distance = 0;
-    let mut depth = 0;
-
-    for command in commands {
-        match command {
-            Command { direction: Direction::Forward, units } => distance += units,
-            Command { direction: Direction::Down, units } => depth += units,
-            Command { direction: Direction::Up, units } => depth -= units,
-        }
-    }
-
-    distance * depth
+fn calculate_position(commands: &[Command]) -> Result<i64, NavigationError> {
+    submarine::run(&mut PlainModel, commands)?.product()
 }
 
 /// position is calculated by using a series of commands
@@ -65,21 +72,36 @@ fn calculate_position(commands: &Vec<Command>) -> u32 {
 ///
 /// Returns distance * depth
 ///
-fn calculate_position_with_aim(commands: &Vec<Command>) -> u32 {
-    let mut distance = 0;
-    let mut depth = 0;
-    let mut aim = 0;
-
-    for command in commands {
-        match command {
-            Command { direction: Direction::Forward, units } => {
-                distance += units;
-                depth += aim * units;
-            },
-            Command { direction: Direction::Down, units } => aim += units,
-            Command { direction: Direction::Up, units } => aim -= units,
-        }
-    }
+fn calculate_position_with_aim(commands: &[Command]) -> Result<i64, NavigationError> {
+    submarine::run(&mut AimModel, commands)?.product()
+}
+
+#[test]
+fn test_sample_input() {
+    let sample_input = String::from("forward 5\r\ndown 5\r\nforward 8\r\nup 3\r\ndown 8\r\nforward 2");
+    let commands = parse(sample_input).unwrap();
+
+    assert_eq!(calculate_position(&commands), Ok(150));
+    assert_eq!(calculate_position_with_aim(&commands), Ok(900));
+}
+
+#[test]
+fn test_above_surface() {
+    let commands = parse(String::from("forward 2\r\nup 5\r\nforward 3")).unwrap();
+
+    assert_eq!(calculate_position(&commands), Ok(-25));
+    assert_eq!(calculate_position_with_aim(&commands), Ok(-75));
+}
+
+#[test]
+fn test_overflow_reports_line() {
+    let commands = parse(format!("forward 1\r\ndown {}\r\ndown 1\r\nforward 2", i64::MAX)).unwrap();
+
+    assert_eq!(calculate_position(&commands), Err(NavigationError::Overflow(3)));
+    assert_eq!(calculate_position_with_aim(&commands), Err(NavigationError::Overflow(3)));
+
+    let commands = parse(format!("down {}\r\nforward 1\r\nforward 2", i64::MAX / 2)).unwrap();
 
-    distance * depth
+    assert_eq!(calculate_position(&commands), Err(NavigationError::ProductOverflow));
+    assert_eq!(calculate_position_with_aim(&commands), Err(NavigationError::Overflow(3)));
 }
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+use crate::{Command, Direction};
+
+/// the most commands a script may expand to, so a large `repeat` can't exhaust memory
+const MAX_COMMANDS: u64 = 10_000_000;
+
+const KEYWORDS: [&str; 6] = ["forward", "back", "down", "up", "repeat", "macro"];
+
+/// a parsed submarine script
+///
+///     # comments run to the end of the line, blank lines are ignored
+///     macro dive {        # macros are defined at the top level, before they are used
+///         down 10
+///         forward 2
+///     }
+///     repeat 3 {          # blocks can be nested
+///         dive
+///         back 1          # same as forward -1
+///     }
+///
+pub struct Script {
+    macros: HashMap<String, Vec<Statement>>,
+    statements: Vec<Statement>
+}
+
+pub enum Statement {
+    Move(Command),
+    Repeat { count: u64, body: Vec<Statement>, line: usize },
+    Call { name: String, line: usize }
+}
+
+/// every error carries the line of the script it was found on
+#[derive(Eq, PartialEq, Debug)]
+pub enum ScriptError {
+    InvalidStatement(usize, String),
+    InvalidUnits(usize, String),
+    InvalidCount(usize, String),
+    UnknownCommand(usize, String),
+    UnexpectedClosingBrace(usize),
+    UnclosedBlock(usize),
+    NestedMacro(usize),
+    DuplicateMacro(usize, String),
+    TooManyCommands(usize)
+}
+
+enum Block {
+    Root,
+    Repeat(u64),
+    Macro(String)
+}
+
+impl Script {
+    pub fn parse(input: &str) -> Result<Script, ScriptError> {
+        let mut macros: HashMap<String, Vec<Statement>> = HashMap::new();
+        // each open block, with the line it was opened on and the statements inside it so far
+        let mut blocks: Vec<(Block, usize, Vec<Statement>)> = vec![(Block::Root, 0, vec![])];
+
+        for (index, raw_line) in input.lines().enumerate() {
+            let line = index + 1;
+            let tokens: Vec<&str> = raw_line.split('#').next().unwrap().split_whitespace().collect();
+
+            let statement = match tokens.as_slice() {
+                [] => continue,
+                ["}"] => {
+                    let (block, opened_on, body) = blocks.pop().unwrap();
+
+                    match block {
+                        Block::Root => return Err(ScriptError::UnexpectedClosingBrace(line)),
+                        Block::Repeat(count) => Statement::Repeat { count, body, line: opened_on },
+                        Block::Macro(name) => {
+                            macros.insert(name, body);
+                            continue;
+                        }
+                    }
+                },
+                ["repeat", count, "{"] => {
+                    let count = count.parse::<u64>().map_err(|_| ScriptError::InvalidCount(line, count.to_string()))?;
+                    blocks.push((Block::Repeat(count), line, vec![]));
+                    continue;
+                },
+                ["macro", name, "{"] => {
+                    if blocks.len() > 1 {
+                        return Err(ScriptError::NestedMacro(line));
+                    }
+                    if KEYWORDS.contains(name) {
+                        return Err(ScriptError::InvalidStatement(line, raw_line.trim().to_string()));
+                    }
+                    if macros.contains_key(*name) {
+                        return Err(ScriptError::DuplicateMacro(line, name.to_string()));
+                    }
+                    blocks.push((Block::Macro(name.to_string()), line, vec![]));
+                    continue;
+                },
+                [direction @ ("forward" | "back" | "down" | "up"), units] => {
+                    let units = units.parse::<i64>().ok()
+                        .and_then(|units| match *direction { "back" => units.checked_neg(), _ => Some(units) })
+                        .ok_or(ScriptError::InvalidUnits(line, units.to_string()))?;
+
+                    let direction = match *direction {
+                        "forward" | "back" => Direction::Forward,
+                        "down" => Direction::Down,
+                        _ => Direction::Up
+                    };
+
+                    Statement::Move(Command { direction, units, line })
+                },
+                [name] if macros.contains_key(*name) => Statement::Call { name: name.to_string(), line },
+                [name] if !KEYWORDS.contains(name) => return Err(ScriptError::UnknownCommand(line, name.to_string())),
+                _ => return Err(ScriptError::InvalidStatement(line, raw_line.trim().to_string()))
+            };
+
+            blocks.last_mut().unwrap().2.push(statement);
+        }
+
+        match blocks.pop().unwrap() {
+            (Block::Root, _, statements) => Ok(Script { macros, statements }),
+            (_, opened_on, _) => Err(ScriptError::UnclosedBlock(opened_on))
+        }
+    }
+
+    /// unrolls every repeat and macro into the plain list of commands
+    pub fn expand(&self) -> Result<Vec<Command>, ScriptError> {
+        let mut total: u64 = 0;
+
+        for statement in self.statements.iter() {
+            total = total.saturating_add(self.count_commands(statement));
+
+            if total > MAX_COMMANDS {
+                return Err(ScriptError::TooManyCommands(statement.get_line()));
+            }
+        }
+
+        let mut commands = Vec::with_capacity(total as usize);
+        self.expand_statements(&self.statements, &mut commands);
+
+        Ok(commands)
+    }
+
+    fn count_commands(&self, statement: &Statement) -> u64 {
+        match statement {
+            Statement::Move(_) => 1,
+            Statement::Repeat { count, body, .. } => {
+                let body_count = body.iter().fold(0u64, |sum, statement| sum.saturating_add(self.count_commands(statement)));
+                body_count.saturating_mul(*count)
+            },
+            Statement::Call { name, .. } => {
+                self.macros[name].iter().fold(0u64, |sum, statement| sum.saturating_add(self.count_commands(statement)))
+            }
+        }
+    }
+
+    fn expand_statements(&self, statements: &[Statement], commands: &mut Vec<Command>) {
+        for statement in statements {
+            match statement {
+                Statement::Move(command) => commands.push(command.clone()),
+                Statement::Repeat { count, body, .. } => {
+                    if self.count_commands(statement) == 0 {
+                        continue;
+                    }
+
+                    for _ in 0..*count {
+                        self.expand_statements(body, commands);
+                    }
+                },
+                Statement::Call { name, .. } => self.expand_statements(&self.macros[name], commands)
+            }
+        }
+    }
+}
+
+impl Statement {
+    fn get_line(&self) -> usize {
+        match self {
+            Statement::Move(command) => command.line,
+            Statement::Repeat { line, .. } | Statement::Call { line, .. } => *line
+        }
+    }
+}
+
+#[cfg(test)]
+fn to_strings(commands: &[Command]) -> Vec<String> {
+    commands.iter()
+        .map(|command| {
+            let direction = match command.direction {
+                Direction::Forward => "forward",
+                Direction::Down => "down",
+                Direction::Up => "up"
+            };
+            format!("{} {} @{}", direction, command.units, command.line)
+        })
+        .collect()
+}
+
+#[test]
+fn test_expand_script() {
+    let script = Script::parse("# warm up\n\nmacro dive {\n    down 10  # steep\n    forward 2\n}\n\nrepeat 2 {\n    dive\n    repeat 2 {\n        back 1\n    }\n}\nup 3\n").unwrap();
+    let commands = script.expand().unwrap();
+
+    assert_eq!(to_strings(&commands), vec![
+        "down 10 @4", "forward 2 @5", "forward -1 @11", "forward -1 @11",
+        "down 10 @4", "forward 2 @5", "forward -1 @11", "forward -1 @11",
+        "up 3 @14",
+    ]);
+}
+
+#[test]
+fn test_script_errors() {
+    let parse_error = |input: &str| Script::parse(input).err().unwrap();
+
+    assert_eq!(parse_error("forward 1\nsideways 3"), ScriptError::InvalidStatement(2, String::from("sideways 3")));
+    assert_eq!(parse_error("forward 1\r\ndive"), ScriptError::UnknownCommand(2, String::from("dive")));
+    assert_eq!(parse_error("down x"), ScriptError::InvalidUnits(1, String::from("x")));
+    assert_eq!(parse_error(&format!("back {}", i64::MIN)), ScriptError::InvalidUnits(1, i64::MIN.to_string()));
+    assert_eq!(parse_error("repeat -1 {\n}"), ScriptError::InvalidCount(1, String::from("-1")));
+    assert_eq!(parse_error("up 1\n}"), ScriptError::UnexpectedClosingBrace(2));
+    assert_eq!(parse_error("up 1\nrepeat 2 {\nup 1"), ScriptError::UnclosedBlock(2));
+    assert_eq!(parse_error("repeat 2 {\nmacro m {\n}\n}"), ScriptError::NestedMacro(2));
+    assert_eq!(parse_error("macro m {\n}\nmacro m {\n}"), ScriptError::DuplicateMacro(3, String::from("m")));
+    assert_eq!(parse_error("macro m {\nm\n}"), ScriptError::UnknownCommand(2, String::from("m")));
+
+    let script = Script::parse("up 1\nrepeat 100000 {\nrepeat 100000 {\nup 1\n}\n}\nrepeat 99999999999 {\n}").unwrap();
+    assert!(Script::parse("repeat 99999999999 {\nrepeat 5 {\n}\n}").unwrap().expand().unwrap().is_empty());
+
+    assert_eq!(script.expand().err().unwrap(), ScriptError::TooManyCommands(2));
+}
@@ -0,0 +1,108 @@
+use crate::{Command, NavigationError};
+use crate::trajectory::Trajectory;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub struct Position {
+    pub distance: i64,
+    pub depth: i64,
+    pub aim: i64
+}
+
+impl Position {
+    pub fn product(&self) -> Result<i64, NavigationError> {
+        self.distance.checked_mul(self.depth).ok_or(NavigationError::ProductOverflow)
+    }
+}
+
+/// an interpretation of the submarine's commands
+///
+/// each method returns the position after moving, or None if the move would overflow.
+/// models take `&mut self` so they can carry state of their own between commands
+pub trait SubmarineModel {
+    fn forward(&mut self, position: Position, units: i64) -> Option<Position>;
+    fn down(&mut self, position: Position, units: i64) -> Option<Position>;
+    fn up(&mut self, position: Position, units: i64) -> Option<Position>;
+}
+
+/// Forward: increase distance by X units
+/// Down: increase depth by X units
+/// Up: decrease depth by X units, possibly above the surface
+pub struct PlainModel;
+
+impl SubmarineModel for PlainModel {
+    fn forward(&mut self, position: Position, units: i64) -> Option<Position> {
+        Some(Position { distance: position.distance.checked_add(units)?, ..position })
+    }
+
+    fn down(&mut self, position: Position, units: i64) -> Option<Position> {
+        Some(Position { depth: position.depth.checked_add(units)?, ..position })
+    }
+
+    fn up(&mut self, position: Position, units: i64) -> Option<Position> {
+        Some(Position { depth: position.depth.checked_sub(units)?, ..position })
+    }
+}
+
+/// Down: increase aim by X units
+/// Up: decreases aim by X units
+/// Forward: increase distance by X, increase depth by aim * X
+pub struct AimModel;
+
+impl SubmarineModel for AimModel {
+    fn forward(&mut self, position: Position, units: i64) -> Option<Position> {
+        Some(Position {
+            distance: position.distance.checked_add(units)?,
+            depth: position.depth.checked_add(position.aim.checked_mul(units)?)?,
+            ..position
+        })
+    }
+
+    fn down(&mut self, position: Position, units: i64) -> Option<Position> {
+        Some(Position { aim: position.aim.checked_add(units)?, ..position })
+    }
+
+    fn up(&mut self, position: Position, units: i64) -> Option<Position> {
+        Some(Position { aim: position.aim.checked_sub(units)?, ..position })
+    }
+}
+
+/// runs every command against the model, starting from the surface
+pub fn run<M: SubmarineModel + ?Sized>(model: &mut M, commands: &[Command]) -> Result<Position, NavigationError> {
+    let mut position = Position::default();
+
+    for next_position in Trajectory::new(model, commands) {
+        position = next_position?;
+    }
+
+    Ok(position)
+}
+
+#[test]
+fn test_custom_model() {
+    /// the plain model, except the submarine can't dive below max_depth
+    struct MaxDepthModel { max_depth: i64 }
+
+    impl SubmarineModel for MaxDepthModel {
+        fn forward(&mut self, position: Position, units: i64) -> Option<Position> {
+            PlainModel.forward(position, units)
+        }
+
+        fn down(&mut self, position: Position, units: i64) -> Option<Position> {
+            let position = PlainModel.down(position, units)?;
+            Some(Position { depth: position.depth.min(self.max_depth), ..position })
+        }
+
+        fn up(&mut self, position: Position, units: i64) -> Option<Position> {
+            PlainModel.up(position, units)
+        }
+    }
+
+    let commands = crate::parse(String::from("forward 5\r\ndown 5\r\nforward 8\r\nup 3\r\ndown 8\r\nforward 2")).unwrap();
+    let models: Vec<Box<dyn SubmarineModel>> = vec![Box::new(PlainModel), Box::new(AimModel), Box::new(MaxDepthModel { max_depth: 4 })];
+
+    let products: Vec<i64> = models.into_iter()
+        .map(|mut model| run(model.as_mut(), &commands).unwrap().product().unwrap())
+        .collect();
+
+    assert_eq!(products, vec![150, 900, 60]);
+}
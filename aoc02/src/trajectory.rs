@@ -0,0 +1,147 @@
+use std::io::{self, Write};
+use std::{iter, slice};
+use crate::{Command, Direction, NavigationError};
+use crate::submarine::{Position, SubmarineModel};
+
+const COLORS: [&str; 6] = ["#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b"];
+
+/// yields the position after every command, stopping after the first command that overflows
+pub struct Trajectory<'a, M: SubmarineModel + ?Sized> {
+    model: &'a mut M,
+    commands: slice::Iter<'a, Command>,
+    position: Option<Position>
+}
+
+impl<'a, M: SubmarineModel + ?Sized> Trajectory<'a, M> {
+    pub fn new(model: &'a mut M, commands: &'a [Command]) -> Trajectory<'a, M> {
+        Trajectory { model, commands: commands.iter(), position: Some(Position::default()) }
+    }
+}
+
+impl<'a, M: SubmarineModel + ?Sized> Iterator for Trajectory<'a, M> {
+    type Item = Result<Position, NavigationError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let position = self.position?;
+        let command = self.commands.next()?;
+
+        self.position = match command.direction {
+            Direction::Forward => self.model.forward(position, command.units),
+            Direction::Down => self.model.down(position, command.units),
+            Direction::Up => self.model.up(position, command.units)
+        };
+
+        Some(self.position.ok_or(NavigationError::Overflow(command.line)))
+    }
+}
+
+/// every position of the run, starting with the surface
+pub fn record<M: SubmarineModel + ?Sized>(model: &mut M, commands: &[Command]) -> Result<Vec<Position>, NavigationError> {
+    let mut positions = vec![Position::default()];
+
+    for position in Trajectory::new(model, commands) {
+        positions.push(position?);
+    }
+
+    Ok(positions)
+}
+
+/// runs each named model and writes one row per position as it goes: model,step,distance,depth,aim
+///
+/// an overflow stops the export with an InvalidData error
+pub fn write_csv<W: Write>(models: &mut [(&str, &mut dyn SubmarineModel)], commands: &[Command], mut writer: W) -> io::Result<()> {
+    writeln!(writer, "model,step,distance,depth,aim")?;
+
+    for (name, model) in models.iter_mut() {
+        let positions = iter::once(Ok(Position::default())).chain(Trajectory::new(&mut **model, commands));
+
+        for (step, position) in positions.enumerate() {
+            let Position { distance, depth, aim } = position
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, format!("{:?}", error)))?;
+
+            writeln!(writer, "{},{},{},{},{}", name, step, distance, depth, aim)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// draws each named path as a polyline of distance against depth, deeper being further down
+pub fn write_svg<W: Write>(paths: &[(&str, Vec<Position>)], mut writer: W) -> io::Result<()> {
+    let all_positions = paths.iter().flat_map(|(_, positions)| positions.iter());
+
+    let (mut min_x, mut max_x, mut min_y, mut max_y) = (0, 0, 0, 0);
+
+    for position in all_positions {
+        min_x = min_x.min(position.distance);
+        max_x = max_x.max(position.distance);
+        min_y = min_y.min(position.depth);
+        max_y = max_y.max(position.depth);
+    }
+
+    let width = (max_x as i128 - min_x as i128).max(1);
+    let height = (max_y as i128 - min_y as i128).max(1);
+
+    writeln!(writer, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"800\" height=\"600\" viewBox=\"{} {} {} {}\" preserveAspectRatio=\"none\">", min_x, min_y, width, height)?;
+
+    for (i, (name, positions)) in paths.iter().enumerate() {
+        let points: Vec<String> = positions.iter()
+            .map(|position| format!("{},{}", position.distance, position.depth))
+            .collect();
+
+        writeln!(writer, "  <polyline fill=\"none\" stroke=\"{}\" stroke-width=\"2\" vector-effect=\"non-scaling-stroke\" points=\"{}\">", COLORS[i % COLORS.len()], points.join(" "))?;
+        writeln!(writer, "    <title>{}</title>", name)?;
+        writeln!(writer, "  </polyline>")?;
+    }
+
+    writeln!(writer, "</svg>")
+}
+
+#[test]
+fn test_trajectory() {
+    use crate::submarine::AimModel;
+
+    let commands = crate::parse(String::from("forward 5\r\ndown 5\r\nforward 8\r\nup 3\r\ndown 8\r\nforward 2")).unwrap();
+    let depths: Vec<(i64, i64)> = Trajectory::new(&mut AimModel, &commands)
+        .map(|position| position.unwrap())
+        .map(|position| (position.depth, position.aim))
+        .collect();
+
+    assert_eq!(depths, vec![(0, 0), (0, 5), (40, 5), (40, 2), (40, 10), (60, 10)]);
+}
+
+#[test]
+fn test_trajectory_stops_on_overflow() {
+    use crate::submarine::PlainModel;
+
+    let commands = crate::parse(format!("down {}\r\ndown 1\r\nforward 1", i64::MAX)).unwrap();
+    let positions: Vec<Result<Position, NavigationError>> = Trajectory::new(&mut PlainModel, &commands).collect();
+
+    assert_eq!(positions.len(), 2);
+    assert_eq!(positions[1], Err(NavigationError::Overflow(2)));
+    assert_eq!(record(&mut PlainModel, &commands), Err(NavigationError::Overflow(2)));
+}
+
+#[test]
+fn test_exports() {
+    use crate::submarine::PlainModel;
+
+    let commands = crate::parse(String::from("forward 2\r\nup 1")).unwrap();
+    let paths = vec![("plain", record(&mut PlainModel, &commands).unwrap())];
+
+    let mut csv = vec![];
+    write_csv(&mut [("plain", &mut PlainModel)], &commands, &mut csv).unwrap();
+
+    assert_eq!(String::from_utf8(csv).unwrap(), "model,step,distance,depth,aim\nplain,0,0,0,0\nplain,1,2,0,0\nplain,2,2,-1,0\n");
+
+    let mut svg = vec![];
+    write_svg(&paths, &mut svg).unwrap();
+    let svg = String::from_utf8(svg).unwrap();
+
+    assert!(svg.contains("viewBox=\"0 -1 2 1\""));
+    assert!(svg.contains("points=\"0,0 2,0 2,-1\""));
+
+    let overflowing = crate::parse(format!("down {}\r\ndown 1", i64::MAX)).unwrap();
+    let error = write_csv(&mut [("plain", &mut PlainModel)], &overflowing, &mut vec![]).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,46 @@
+/// a binary number of any width, most significant bit first
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Bits {
+    bits: Vec<bool>
+}
+
+impl Bits {
+    pub fn from_str(s: &str) -> Bits {
+        Bits { bits: s.chars().map(|bit| bit == '1').collect() }
+    }
+
+    pub fn from_bits(bits: Vec<bool>) -> Bits {
+        Bits { bits }
+    }
+
+    pub fn to_binary_string(&self) -> String {
+        self.bits.iter().map(|bit| match bit { true => '1', false => '0' }).collect()
+    }
+
+    /// the value as an integer, or None if it needs more than 128 bits (leading zeros don't count)
+    pub fn to_u128(&self) -> Option<u128> {
+        let first_one = self.bits.iter().position(|bit| *bit).unwrap_or(self.bits.len());
+        let significant_bits = &self.bits[first_one..];
+
+        if significant_bits.len() > 128 {
+            return None;
+        }
+
+        Some(significant_bits.iter().fold(0, |value, bit| (value << 1) | *bit as u128))
+    }
+
+    /// self * other, if both values and their product fit in a u128
+    pub fn checked_mul(&self, other: &Bits) -> Option<u128> {
+        self.to_u128()?.checked_mul(other.to_u128()?)
+    }
+}
+
+#[test]
+fn test_to_u128() {
+    assert_eq!(Bits::from_str("10110").to_u128(), Some(22));
+    assert_eq!(Bits::from_str("").to_u128(), Some(0));
+    assert_eq!(Bits::from_str(&format!("{}1", "0".repeat(200))).to_u128(), Some(1));
+    assert_eq!(Bits::from_str(&"1".repeat(128)).to_u128(), Some(u128::MAX));
+    assert_eq!(Bits::from_str(&"1".repeat(129)).to_u128(), None);
+    assert_eq!(Bits::from_str(&"1".repeat(129)).to_binary_string(), "1".repeat(129));
+}
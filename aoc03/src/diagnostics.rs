@@ -0,0 +1,94 @@
+use std::fmt;
+
+/// which bit counts as the most common when a place has as many 1s as 0s
+///
+/// PreferOne is the puzzle's rule: gamma and the oxygen generator rating keep the 1,
+/// epsilon and the CO2 scrubber rating keep the 0
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum TieBreak {
+    PreferOne,
+    PreferZero,
+    Error
+}
+
+/// lines and columns count from 1, bit places count from 0 at the most significant bit
+#[derive(Eq, PartialEq, Debug)]
+pub enum DiagnosticsError {
+    EmptyReport,
+    InconsistentWidth(usize, usize, usize),
+    InvalidBit(usize, usize, char),
+    Tie(usize)
+}
+
+impl TieBreak {
+    pub fn from_str(s: &str) -> TieBreak {
+        match s {
+            "one" => TieBreak::PreferOne,
+            "zero" => TieBreak::PreferZero,
+            "error" => TieBreak::Error,
+            _ => panic!("invalid tie break")
+        }
+    }
+
+    /// true if 1 is the most common bit at this place
+    pub fn is_one_most_common(&self, ones: usize, zeros: usize, bit: usize) -> Result<bool, DiagnosticsError> {
+        match (ones.cmp(&zeros), self) {
+            (std::cmp::Ordering::Greater, _) => Ok(true),
+            (std::cmp::Ordering::Less, _) => Ok(false),
+            (_, TieBreak::PreferOne) => Ok(true),
+            (_, TieBreak::PreferZero) => Ok(false),
+            (_, TieBreak::Error) => Err(DiagnosticsError::Tie(bit))
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiagnosticsError::EmptyReport => write!(f, "the report has no entries"),
+            DiagnosticsError::InconsistentWidth(line, expected, found) => write!(f, "line {}: expected {} bits, found {}", line, expected, found),
+            DiagnosticsError::InvalidBit(line, column, found) => write!(f, "line {}, column {}: expected 0 or 1, found {:?}", line, column, found),
+            DiagnosticsError::Tie(bit) => write!(f, "bit {}: as many 1s as 0s", bit)
+        }
+    }
+}
+
+/// checks every entry is made of only 0s and 1s and is as wide as the first, returning that width
+pub fn validate_report(diagnostics_report: &[&str]) -> Result<usize, DiagnosticsError> {
+    let width = match diagnostics_report.first() {
+        Some(entry) if !entry.is_empty() => entry.chars().count(),
+        _ => return Err(DiagnosticsError::EmptyReport)
+    };
+
+    for (index, entry) in diagnostics_report.iter().enumerate() {
+        if let Some((column, c)) = entry.chars().enumerate().find(|(_, c)| *c != '0' && *c != '1') {
+            return Err(DiagnosticsError::InvalidBit(index + 1, column + 1, c));
+        }
+
+        if entry.len() != width {
+            return Err(DiagnosticsError::InconsistentWidth(index + 1, width, entry.len()));
+        }
+    }
+
+    Ok(width)
+}
+
+#[test]
+fn test_validate_report() {
+    assert_eq!(validate_report(&["0101", "1100"]), Ok(4));
+    assert_eq!(validate_report(&[]), Err(DiagnosticsError::EmptyReport));
+    assert_eq!(validate_report(&[""]), Err(DiagnosticsError::EmptyReport));
+    assert_eq!(validate_report(&["0101", "110", "1100"]), Err(DiagnosticsError::InconsistentWidth(2, 4, 3)));
+    assert_eq!(validate_report(&["0101", "11001"]), Err(DiagnosticsError::InconsistentWidth(2, 4, 5)));
+    assert_eq!(validate_report(&["0101", "1100", "10é1"]), Err(DiagnosticsError::InvalidBit(3, 3, 'é')));
+    assert_eq!(validate_report(&["01 1"]).unwrap_err().to_string(), "line 1, column 3: expected 0 or 1, found ' '");
+}
+
+#[test]
+fn test_tie_break() {
+    assert_eq!(TieBreak::Error.is_one_most_common(3, 2, 0), Ok(true));
+    assert_eq!(TieBreak::Error.is_one_most_common(2, 3, 0), Ok(false));
+    assert_eq!(TieBreak::PreferOne.is_one_most_common(2, 2, 0), Ok(true));
+    assert_eq!(TieBreak::PreferZero.is_one_most_common(2, 2, 0), Ok(false));
+    assert_eq!(TieBreak::Error.is_one_most_common(2, 2, 4), Err(DiagnosticsError::Tie(4)));
+}
@@ -0,0 +1,71 @@
+use crate::bits::Bits;
+use crate::diagnostics::{self, DiagnosticsError, TieBreak};
+
+/// the diagnostics report sorted once, so every entry sharing a prefix sits in one contiguous range
+///
+/// within a range sharing the first n bits, entries with a 0 at bit n all come before the
+/// entries with a 1, so finding a rating is a walk of one binary search per bit
+pub struct ReportIndex<'a> {
+    entries: Vec<&'a str>,
+    width: usize
+}
+
+impl<'a> ReportIndex<'a> {
+    pub fn new(diagnostics_report: &[&'a str]) -> Result<ReportIndex<'a>, DiagnosticsError> {
+        let width = diagnostics::validate_report(diagnostics_report)?;
+
+        let mut entries = diagnostics_report.to_vec();
+        entries.sort_unstable();
+
+        Ok(ReportIndex { entries, width })
+    }
+
+    /// keeps the most (or least) common bit at each place, as decided by tie_break on a tie
+    ///
+    /// once a single entry is left, or every remaining entry shares the next bit, that bit is
+    /// the only way forward
+    pub fn find_rating(&self, most_common: bool, tie_break: TieBreak) -> Result<Bits, DiagnosticsError> {
+        let (mut start, mut end) = (0, self.entries.len());
+
+        for bit in 0..self.width {
+            let split = start + self.entries[start..end].partition_point(|entry| entry.as_bytes()[bit] != b'1');
+
+            let zeros = split - start;
+            let ones = end - split;
+
+            let keep_ones = match (zeros, ones) {
+                (0, _) => true,
+                (_, 0) => false,
+                _ => tie_break.is_one_most_common(ones, zeros, bit)? == most_common
+            };
+
+            match keep_ones {
+                true => start = split,
+                false => end = split
+            }
+        }
+
+        Ok(Bits::from_str(self.entries[start]))
+    }
+}
+
+#[test]
+fn test_sample_input() {
+    let report = vec!["00100", "11110", "10110", "10111", "10101", "01111", "00111", "11100", "10000", "11001", "00010", "01010"];
+    let index = ReportIndex::new(&report).unwrap();
+
+    assert_eq!(index.find_rating(true, TieBreak::PreferOne).unwrap().to_u128(), Some(23));
+    assert_eq!(index.find_rating(false, TieBreak::PreferOne).unwrap().to_u128(), Some(10));
+    assert_eq!(index.find_rating(true, TieBreak::PreferZero).unwrap().to_binary_string(), "10110");
+    assert_eq!(index.find_rating(false, TieBreak::PreferZero).unwrap().to_binary_string(), "01111");
+    assert_eq!(index.find_rating(true, TieBreak::Error), Err(DiagnosticsError::Tie(4)));
+    assert_eq!(index.find_rating(false, TieBreak::Error), Err(DiagnosticsError::Tie(2)));
+}
+
+#[test]
+fn test_duplicate_entries() {
+    let index = ReportIndex::new(&["101", "101", "101", "010"]).unwrap();
+
+    assert_eq!(index.find_rating(true, TieBreak::Error).unwrap().to_binary_string(), "101");
+    assert_eq!(index.find_rating(false, TieBreak::Error).unwrap().to_binary_string(), "010");
+}
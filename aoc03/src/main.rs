@@ -1,9 +1,17 @@
-use std::fs;
+mod bits;
+mod diagnostics;
+mod index;
+
+use std::{env, fs};
+use std::time::Instant;
+use bits::Bits;
+use diagnostics::{DiagnosticsError, TieBreak};
+use index::ReportIndex;
 
 #[derive(Clone)]
 struct BitCount {
-    one: u32,
-    zero: u32
+    one: usize,
+    zero: usize
 }
 
 impl BitCount {
@@ -14,7 +22,8 @@ impl BitCount {
     fn add_bit(&mut self, bit: char) {
         match bit {
             '1' => self.one += 1,
-            _ => self.zero += 1
+            '0' => self.zero += 1,
+            _ => panic!("invalid bit")
         }
     }
 }
@@ -27,19 +36,42 @@ fn main() {
         .split("\r\n")
         .collect();
 
-    println!("answer 3.1: {}", calculate_power_consumption(&diagnostics_report));
-    println!("answer 3.2: {}", calculate_life_support_rating(&diagnostics_report));
+    let tie_break = env::args()
+        .find_map(|arg| arg.strip_prefix("--tie-break=").map(TieBreak::from_str))
+        .unwrap_or(TieBreak::PreferOne);
+
+    match calculate_gamma_and_epsilon_rates(&diagnostics_report, tie_break) {
+        Ok((gamma_rate, epsilon_rate)) => println!("answer 3.1: {}", format_product(&gamma_rate, &epsilon_rate)),
+        Err(error) => println!("answer 3.1: {}", error)
+    }
+
+    match calculate_life_support_ratings(&diagnostics_report, tie_break) {
+        Ok((oxygen_generator_rating, co2_scrubber_rating)) => println!("answer 3.2: {}", format_product(&oxygen_generator_rating, &co2_scrubber_rating)),
+        Err(error) => println!("answer 3.2: {}", error)
+    }
+
+    if env::args().any(|arg| arg == "--bench") {
+        benchmark_rating_strategies();
+    }
+}
+
+/// the product of both values, or both values in binary if it doesn't fit in a u128
+fn format_product(a: &Bits, b: &Bits) -> String {
+    match a.checked_mul(b) {
+        Some(product) => product.to_string(),
+        None => format!("{} * {}", a.to_binary_string(), b.to_binary_string())
+    }
 }
 
 /// given a list of binary numbers, find the most common bits for each bit place
 ///
-/// if 1 is the most common bit for a given place, add the bit's value to gamma_rate
-/// if 0 is the most common bit for a given place, add the bit's value to epsilon_rate
+/// if 1 is the most common bit for a given place, gamma_rate has a 1 in that place
+/// if 0 is the most common bit for a given place, epsilon_rate has a 1 in that place
 ///
-/// returns gamma_rate * epsilon_rate
+/// returns (gamma_rate, epsilon_rate), which can be any number of bits wide
 ///
-fn calculate_power_consumption(diagnostics_report: &Vec<&str>) -> u32 {
-    let bit_length = diagnostics_report[0].len();
+fn calculate_gamma_and_epsilon_rates(diagnostics_report: &[&str], tie_break: TieBreak) -> Result<(Bits, Bits), DiagnosticsError> {
+    let bit_length = diagnostics::validate_report(diagnostics_report)?;
     let mut bit_counts = vec![BitCount::new(); bit_length];
 
     for entry in diagnostics_report {
@@ -48,39 +80,30 @@ fn calculate_power_consumption(diagnostics_report: &Vec<&str>) -> u32 {
         }
     }
 
-    let mut gamma_rate = 0;
-    let mut epsilon_rate = 0;
+    let gamma_bits = bit_counts.iter()
+        .enumerate()
+        .map(|(i, BitCount { one, zero })| tie_break.is_one_most_common(*one, *zero, i))
+        .collect::<Result<Vec<bool>, DiagnosticsError>>()?;
 
-    for (i, bit_count) in bit_counts.iter().enumerate() {
-        let bit_value = 1 << (bit_length - 1) - i;
-
-        match bit_count {
-            BitCount { one, zero } if one >= zero => gamma_rate += bit_value,
-            _ => epsilon_rate += bit_value,
-        }
-    }
+    let epsilon_bits = gamma_bits.iter().map(|bit| !bit).collect();
 
-    gamma_rate * epsilon_rate
+    Ok((Bits::from_bits(gamma_bits), Bits::from_bits(epsilon_bits)))
 }
 
-fn calculate_life_support_rating(diagnostics_report: &Vec<&str>) -> u32 {
-    let oxygen_generator_rating = calculate_oxygen_generator_rating(diagnostics_report);
-    let co2_scrubber_rating = calculate_co2_scrubber_rating(diagnostics_report);
+/// returns (oxygen_generator_rating, co2_scrubber_rating), found by walking a sorted index of the report
+fn calculate_life_support_ratings(diagnostics_report: &[&str], tie_break: TieBreak) -> Result<(Bits, Bits), DiagnosticsError> {
+    let index = ReportIndex::new(diagnostics_report)?;
 
-    oxygen_generator_rating * co2_scrubber_rating
+    Ok((index.find_rating(true, tie_break)?, index.find_rating(false, tie_break)?))
 }
 
-fn calculate_oxygen_generator_rating(diagnostics_report: &Vec<&str>) -> u32 {
-    calculate_rating(diagnostics_report, 0, true)
-}
-
-fn calculate_co2_scrubber_rating(diagnostics_report: &Vec<&str>) -> u32 {
-    calculate_rating(diagnostics_report, 0, false)
-}
-
-fn calculate_rating(diagnostics_report: &Vec<&str>, start_bit: usize, most_common: bool) -> u32 {
+/// the original rating search: filters (and copies) the remaining report once per bit
+///
+/// kept to check the index against, see benchmark_rating_strategies. it expects a
+/// validated report and always breaks ties like TieBreak::PreferOne
+fn calculate_rating(diagnostics_report: &[&str], start_bit: usize, most_common: bool) -> Bits {
     if diagnostics_report.len() == 1 {
-        return u32::from_str_radix(diagnostics_report[0], 2).unwrap();
+        return Bits::from_str(diagnostics_report[0]);
     }
 
     let common_bit = get_common_bit(diagnostics_report, start_bit, most_common);
@@ -91,10 +114,15 @@ fn calculate_rating(diagnostics_report: &Vec<&str>, start_bit: usize, most_commo
         .copied()
         .collect();
 
+    // every entry shares this bit, so the least common bit matched nothing
+    if filtered_report.is_empty() {
+        return calculate_rating(diagnostics_report, start_bit + 1, most_common);
+    }
+
     calculate_rating(&filtered_report, start_bit + 1, most_common)
 }
 
-fn get_common_bit(diagnostics_report: &Vec<&str>, start_bit: usize, most_common: bool) -> char {
+fn get_common_bit(diagnostics_report: &[&str], start_bit: usize, most_common: bool) -> char {
     let mut bit_count = BitCount::new();
 
     for entry in diagnostics_report {
@@ -107,6 +135,52 @@ fn get_common_bit(diagnostics_report: &Vec<&str>, start_bit: usize, most_common:
     }
 }
 
+/// times the recursive filter against sorting and walking a ReportIndex, on generated reports
+fn benchmark_rating_strategies() {
+    for (entries, width) in [(1_000, 12), (100_000, 40), (1_000_000, 64)] {
+        let generated_report = generate_report(entries, width, 2021);
+        let diagnostics_report: Vec<&str> = generated_report.iter().map(|entry| entry.as_str()).collect();
+
+        let start = Instant::now();
+        let filtered = (calculate_rating(&diagnostics_report, 0, true), calculate_rating(&diagnostics_report, 0, false));
+        let filter_time = start.elapsed();
+
+        let start = Instant::now();
+        let index = ReportIndex::new(&diagnostics_report).unwrap();
+        let build_time = start.elapsed();
+        let walked = (index.find_rating(true, TieBreak::PreferOne).unwrap(), index.find_rating(false, TieBreak::PreferOne).unwrap());
+        let walk_time = start.elapsed() - build_time;
+
+        assert_eq!(filtered, walked);
+
+        println!("{:>8} entries x {:>2} bits: filter {:>10.3?}, sorted index {:>10.3?} + walk {:>8.3?}", entries, width, filter_time, build_time, walk_time);
+    }
+}
+
+/// a report of distinct pseudo-random entries, using xorshift so runs are repeatable
+fn generate_report(entries: usize, width: usize, seed: u64) -> Vec<String> {
+    let mut state = seed.max(1);
+    let mut seen = std::collections::HashSet::new();
+    let mut report = vec![];
+
+    while report.len() < entries {
+        let entry: String = (0..width)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                match state & 1 { 1 => '1', _ => '0' }
+            })
+            .collect();
+
+        if seen.insert(entry.clone()) {
+            report.push(entry);
+        }
+    }
+
+    report
+}
+
 /// Test values from the problem
 #[test]
 fn test_calculate_ratings() {
@@ -125,6 +199,57 @@ fn test_calculate_ratings() {
         "01010"
     ];
 
-    assert_eq!(calculate_oxygen_generator_rating(&test_diagnostics_report), 23);
-    assert_eq!(calculate_co2_scrubber_rating(&test_diagnostics_report), 10);
+    let (oxygen_generator_rating, co2_scrubber_rating) = calculate_life_support_ratings(&test_diagnostics_report, TieBreak::PreferOne).unwrap();
+    let (gamma_rate, epsilon_rate) = calculate_gamma_and_epsilon_rates(&test_diagnostics_report, TieBreak::Error).unwrap();
+
+    assert_eq!(oxygen_generator_rating.to_u128(), Some(23));
+    assert_eq!(co2_scrubber_rating.to_u128(), Some(10));
+    assert_eq!(gamma_rate.checked_mul(&epsilon_rate), Some(198));
+    assert_eq!(format_product(&oxygen_generator_rating, &co2_scrubber_rating), "230");
+}
+
+#[test]
+fn test_wide_report() {
+    let one = format!("1{}", "0".repeat(99));
+    let two = format!("1{}", "1".repeat(99));
+    let three = format!("0{}", "1".repeat(99));
+    let wide_report = vec![one.as_str(), two.as_str(), three.as_str()];
+
+    let (gamma_rate, epsilon_rate) = calculate_gamma_and_epsilon_rates(&wide_report, TieBreak::Error).unwrap();
+
+    assert_eq!(gamma_rate.to_binary_string(), two);
+    assert_eq!(epsilon_rate.to_u128(), Some(0));
+    assert_eq!(gamma_rate.checked_mul(&epsilon_rate), Some(0));
+
+    let (oxygen_generator_rating, co2_scrubber_rating) = calculate_life_support_ratings(&wide_report, TieBreak::PreferOne).unwrap();
+
+    assert_eq!(oxygen_generator_rating.to_binary_string(), two);
+    assert_eq!(co2_scrubber_rating.to_binary_string(), three);
+    assert_eq!(oxygen_generator_rating.checked_mul(&co2_scrubber_rating), None);
+    assert_eq!(format_product(&oxygen_generator_rating, &co2_scrubber_rating), format!("{} * {}", two, three));
+    assert_eq!(format_product(&Bits::from_str("11"), &Bits::from_str("101")), "15");
+}
+
+#[test]
+fn test_index_matches_recursive_filter() {
+    for (entries, width) in [(2, 3), (50, 8), (3_000, 24)] {
+        let generated_report = generate_report(entries, width, entries as u64);
+        let diagnostics_report: Vec<&str> = generated_report.iter().map(|entry| entry.as_str()).collect();
+        let index = ReportIndex::new(&diagnostics_report).unwrap();
+
+        assert_eq!(index.find_rating(true, TieBreak::PreferOne), Ok(calculate_rating(&diagnostics_report, 0, true)));
+        assert_eq!(index.find_rating(false, TieBreak::PreferOne), Ok(calculate_rating(&diagnostics_report, 0, false)));
+    }
+}
+
+#[test]
+fn test_tie_breaks_and_validation() {
+    let tied_report = vec!["110", "011", "101", "000"];
+
+    assert_eq!(calculate_gamma_and_epsilon_rates(&tied_report, TieBreak::PreferOne).unwrap().0.to_binary_string(), "111");
+    assert_eq!(calculate_gamma_and_epsilon_rates(&tied_report, TieBreak::PreferZero).unwrap().0.to_binary_string(), "000");
+    assert_eq!(calculate_gamma_and_epsilon_rates(&tied_report, TieBreak::Error), Err(DiagnosticsError::Tie(0)));
+
+    assert_eq!(calculate_gamma_and_epsilon_rates(&["101", "1O1"], TieBreak::PreferOne), Err(DiagnosticsError::InvalidBit(2, 2, 'O')));
+    assert_eq!(calculate_life_support_ratings(&["101", "10"], TieBreak::PreferOne), Err(DiagnosticsError::InconsistentWidth(2, 3, 2)));
 }
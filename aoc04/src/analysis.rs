@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use crate::bingo::{Bingo, Position};
+use crate::game::WinEvent;
+
+/// the index of the first time each number is drawn
+pub fn index_draws(number_draws: &[u32]) -> HashMap<u32, usize> {
+    let mut draw_indices = HashMap::with_capacity(number_draws.len());
+
+    for (draw_index, draw) in number_draws.iter().copied().enumerate() {
+        draw_indices.entry(draw).or_insert(draw_index);
+    }
+
+    draw_indices
+}
+
+/// works out when and how a board wins without marking anything: each line is complete at the
+/// draw of its last number, and the board wins at the earliest of those
+pub fn analyze_board(board_index: usize, board: &Bingo, number_draws: &[u32], draw_indices: &HashMap<u32, usize>) -> Option<WinEvent> {
+    let (height, width) = board.get_dimensions();
+    let mut marked_at: Vec<Vec<Option<usize>>> = vec![vec![None; width]; height];
+
+    for (number, position) in board.get_number_positions() {
+        marked_at[position.row][position.col] = draw_indices.get(&number).copied();
+    }
+
+    let draw_index = board.get_lines().iter()
+        .filter_map(|line| {
+            line.iter()
+                .map(|position| marked_at[position.row][position.col])
+                .try_fold(0, |latest, index| Some(latest.max(index?)))
+        })
+        .min()?;
+
+    let draw = number_draws[draw_index];
+
+    let sum_of_unmarked_numbers: u32 = marked_at.iter()
+        .enumerate()
+        .flat_map(|(row, indices)| indices.iter().enumerate().map(move |(col, index)| (row, col, index)))
+        .filter(|(_, _, index)| index.is_none_or(|index| index > draw_index))
+        .map(|(row, col, _)| board.get_number(&Position { row, col }))
+        .sum();
+
+    Some(WinEvent { board_index, draw, draw_index, score: sum_of_unmarked_numbers * draw })
+}
+
+/// every board that wins, in the order they would win in a game, leaving out boards that never do
+pub fn rank_boards(number_draws: &[u32], boards: &[Bingo]) -> Vec<WinEvent> {
+    let draw_indices = index_draws(number_draws);
+
+    let mut win_events: Vec<WinEvent> = boards.iter()
+        .enumerate()
+        .filter_map(|(board_index, board)| analyze_board(board_index, board, number_draws, &draw_indices))
+        .collect();
+
+    win_events.sort_by_key(|event| (event.draw_index, event.board_index));
+    win_events
+}
+
+#[test]
+fn test_matches_simulation() {
+    use crate::game::{sample_game, BingoGame};
+
+    let (number_draws, boards) = sample_game();
+    let ranking = rank_boards(&number_draws, &boards);
+
+    assert_eq!(ranking, BingoGame::play(&number_draws, boards).get_win_events());
+    assert_eq!(rank_boards(&number_draws[0..12], &sample_game().1).len(), 1);
+}
+
+#[test]
+fn test_matches_simulation_with_other_rules() {
+    use crate::bingo::WinRule;
+    use crate::game::BingoGame;
+
+    // repeats and numbers missing from every board shouldn't change anything
+    let number_draws: Vec<u32> = (0..200).map(|i| (i * 37 + 11) % 90).collect();
+    let rule_sets = [vec![WinRule::Diagonals], vec![WinRule::Corners, WinRule::Rows], vec![WinRule::FullCard]];
+
+    // boards one row or column wide have corners that overlap
+    let shapes = [(4u32, 4u32), (1, 3), (3, 1), (1, 1)];
+
+    for (rules, (height, width)) in rule_sets.iter().flat_map(|rules| shapes.iter().map(move |shape| (rules, *shape))) {
+        let boards = || -> Vec<Bingo> {
+            (0..20u32)
+                .map(|board| (0..height).map(|row| (0..width).map(|col| (board * 11 + (row * width + col) * 17) % 64).collect()).collect())
+                .map(|board_data: Vec<Vec<u32>>| Bingo::with_rules(board_data, rules))
+                .collect()
+        };
+
+        assert_eq!(rank_boards(&number_draws, &boards()), BingoGame::play(&number_draws, boards()).get_win_events());
+    }
+
+    let board = || vec![Bingo::with_rules(vec![vec![1, 2, 3]], &[WinRule::Corners])];
+    let ranking = rank_boards(&[1, 3], &board());
+
+    assert_eq!(ranking.len(), 1);
+    assert_eq!(ranking, BingoGame::play(&[1, 3], board()).get_win_events());
+}
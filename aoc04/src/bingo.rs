@@ -1,21 +1,91 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 
+/// a way of completing a board, any number of which can be combined
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum WinRule {
+    Rows,
+    Columns,
+    /// both diagonals, which only square boards have
+    Diagonals,
+    Corners,
+    FullCard
+}
+
 #[derive(Clone)]
 pub struct Position {
     pub row: usize,
     pub col: usize
 }
 
+#[derive(Clone)]
 pub struct Bingo {
     board: Vec<Vec<u32>>,
     number_position_map: HashMap<u32, Position>,
     marked_numbers: HashSet<u32>,
-    won: bool
+    lines: Vec<Vec<Position>>,
+    winning_line: Option<usize>
+}
+
+impl WinRule {
+    pub fn from_str(s: &str) -> WinRule {
+        match s {
+            "rows" => WinRule::Rows,
+            "columns" => WinRule::Columns,
+            "diagonals" => WinRule::Diagonals,
+            "corners" => WinRule::Corners,
+            "full" => WinRule::FullCard,
+            _ => panic!("invalid win rule")
+        }
+    }
+
+    /// the puzzle's rules: any complete row or column
+    pub fn standard() -> Vec<WinRule> {
+        vec![WinRule::Rows, WinRule::Columns]
+    }
+
+    fn get_lines(&self, height: usize, width: usize) -> Vec<Vec<Position>> {
+        match self {
+            WinRule::Rows => (0..height)
+                .map(|row| (0..width).map(|col| Position { row, col }).collect())
+                .collect(),
+            WinRule::Columns => (0..width)
+                .map(|col| (0..height).map(|row| Position { row, col }).collect())
+                .collect(),
+            WinRule::Diagonals if height == width => vec![
+                (0..height).map(|i| Position { row: i, col: i }).collect(),
+                (0..height).map(|i| Position { row: i, col: width - 1 - i }).collect()
+            ],
+            WinRule::Diagonals => vec![],
+            WinRule::Corners => {
+                // a board one row or column wide has fewer than four distinct corners
+                let mut corners = vec![(0, 0), (0, width - 1), (height - 1, 0), (height - 1, width - 1)];
+                corners.sort();
+                corners.dedup();
+
+                vec![corners.into_iter().map(|(row, col)| Position { row, col }).collect()]
+            }
+            WinRule::FullCard => vec![(0..height)
+                .flat_map(|row| (0..width).map(move |col| Position { row, col }))
+                .collect()]
+        }
+    }
 }
 
 impl Bingo {
+    /// a board with the puzzle's rules, which the tests build most of their boards with
+    #[cfg(test)]
     pub fn new(board: Vec<Vec<u32>>) -> Bingo {
+        Bingo::with_rules(board, &WinRule::standard())
+    }
+
+    /// a board of any rectangular size that wins when any line of the given rules is complete
+    pub fn with_rules(board: Vec<Vec<u32>>, rules: &[WinRule]) -> Bingo {
+        let height = board.len();
+        let width = board.first().map_or(0, |row| row.len());
+
+        assert!(width > 0, "a board needs at least one number");
+        assert!(board.iter().all(|row| row.len() == width), "every row of a board must be the same width");
 
         let number_position_map: HashMap<u32, Position> = board.iter()
             .enumerate()
@@ -29,11 +99,42 @@ impl Bingo {
 
         let marked_numbers = HashSet::new();
 
-        Bingo { board, number_position_map, marked_numbers, won: false }
+        let lines = rules.iter()
+            .flat_map(|rule| rule.get_lines(height, width))
+            .collect();
+
+        Bingo { board, number_position_map, marked_numbers, lines, winning_line: None }
+    }
+
+    /// (height, width)
+    pub fn get_dimensions(&self) -> (usize, usize) {
+        (self.board.len(), self.board[0].len())
+    }
+
+    pub fn get_number(&self, position: &Position) -> u32 {
+        self.board[position.row][position.col]
+    }
+
+    pub fn get_number_positions(&self) -> impl Iterator<Item = (u32, &Position)> {
+        self.number_position_map.iter().map(|(number, position)| (*number, position))
+    }
+
+    /// every line of positions that wins the board once all of it is marked
+    pub fn get_lines(&self) -> &[Vec<Position>] {
+        &self.lines
     }
 
     pub fn has_won(&self) -> bool {
-        self.won
+        self.winning_line.is_some()
+    }
+
+    /// the first complete line, once the board has won
+    pub fn get_winning_line(&self) -> Option<&[Position]> {
+        self.winning_line.map(|line| self.lines[line].as_slice())
+    }
+
+    pub fn is_marked(&self, position: &Position) -> bool {
+        self.is_number_marked(&self.get_number(position))
     }
 
     pub fn mark_number(&mut self, number: u32) {
@@ -43,7 +144,7 @@ impl Bingo {
 
         self.marked_numbers.insert(number);
 
-        if self.get_marked_number_count() > 4 {
+        if self.get_marked_number_count() >= self.get_shortest_line_length() {
             self.check_bingo(number);
         }
     }
@@ -59,17 +160,21 @@ impl Bingo {
     fn check_bingo(&mut self, last_inserted_number: u32) {
         let Position { row, col } = self.get_position(last_inserted_number).unwrap();
 
-        if self.is_row_winning(row) || self.is_col_winning(col) {
-            self.won = true;
+        let winning_line = self.lines.iter()
+            .position(|line| line.iter().any(|position| position.row == row && position.col == col) && self.is_line_winning(line));
+
+        if self.winning_line.is_none() {
+            self.winning_line = winning_line;
         }
     }
 
-    fn is_row_winning(&self, row: usize) -> bool {
-        self.board[row][0..5].iter().all(|number| self.is_number_marked(number))
+    fn is_line_winning(&self, line: &[Position]) -> bool {
+        line.iter().all(|Position { row, col }| self.is_number_marked(&self.board[*row][*col]))
     }
 
-    fn is_col_winning(&self, col: usize) -> bool {
-        self.board[0..5].iter().all(|row| self.is_number_marked(&row[col]))
+    /// no line repeats a position, so a board can't win before this many of its numbers are marked
+    fn get_shortest_line_length(&self) -> usize {
+        self.lines.iter().map(|line| line.len()).min().unwrap_or(usize::MAX)
     }
 
     fn get_marked_number_count(&self) -> usize {
@@ -89,10 +194,7 @@ impl Bingo {
     }
 
     fn is_number_marked(&self, number: &u32) -> bool {
-        match self.marked_numbers.get(number) {
-            Some(_) => true,
-            None => false
-        }
+        self.marked_numbers.contains(number)
     }
 }
 
@@ -130,7 +232,7 @@ fn test_sample_input() {
     bingo.mark_number(21);
     bingo.mark_number(24);
 
-    assert_eq!(bingo.has_won(), true);
+    assert!(bingo.has_won());
     assert_eq!(bingo.sum_of_unmarked_numbers(), 188);
 }
 
@@ -152,5 +254,86 @@ fn test_col_win() {
     bingo.mark_number(22);
     bingo.mark_number(2);
 
-    assert_eq!(bingo.has_won(), true);
+    assert!(bingo.has_won());
+}
+
+#[cfg(test)]
+fn mark_all(bingo: &mut Bingo, numbers: &[u32]) {
+    for number in numbers {
+        bingo.mark_number(*number);
+    }
+}
+
+#[test]
+fn test_diagonal_and_corner_wins() {
+    let test_board = vec![
+        vec![1, 2, 3],
+        vec![4, 5, 6],
+        vec![7, 8, 9]
+    ];
+
+    let mut bingo = Bingo::with_rules(test_board.clone(), &[WinRule::Diagonals]);
+    mark_all(&mut bingo, &[1, 2, 3, 5, 9]);
+    assert!(bingo.has_won());
+
+    let mut bingo = Bingo::with_rules(test_board.clone(), &[WinRule::Diagonals]);
+    mark_all(&mut bingo, &[1, 2, 3, 4, 7]);
+    assert!(!bingo.has_won());
+    mark_all(&mut bingo, &[5]);
+    assert!(bingo.has_won());
+
+    let mut bingo = Bingo::with_rules(test_board.clone(), &[WinRule::Corners]);
+    mark_all(&mut bingo, &[1, 3, 5, 7]);
+    assert!(!bingo.has_won());
+    mark_all(&mut bingo, &[9]);
+    assert!(bingo.has_won());
+    assert_eq!(bingo.sum_of_unmarked_numbers(), 2 + 4 + 6 + 8);
+}
+
+#[test]
+fn test_rectangular_boards() {
+    let test_board = vec![
+        vec![1, 2, 3, 4],
+        vec![5, 6, 7, 8]
+    ];
+
+    let mut bingo = Bingo::new(test_board.clone());
+    mark_all(&mut bingo, &[2]);
+    assert!(!bingo.has_won());
+    mark_all(&mut bingo, &[6]);
+    assert!(bingo.has_won());
+
+    // a rectangular board has no diagonals, so it can never win on them
+    let mut bingo = Bingo::with_rules(test_board.clone(), &[WinRule::Diagonals]);
+    mark_all(&mut bingo, &[1, 2, 3, 4, 5, 6, 7, 8]);
+    assert!(!bingo.has_won());
+
+    let mut bingo = Bingo::with_rules(test_board, &[WinRule::FullCard]);
+    mark_all(&mut bingo, &[1, 2, 3, 4, 5, 6, 7]);
+    assert!(!bingo.has_won());
+    mark_all(&mut bingo, &[8]);
+    assert!(bingo.has_won());
+    assert_eq!(bingo.sum_of_unmarked_numbers(), 0);
+}
+
+#[test]
+fn test_corners_of_thin_boards() {
+    let mut bingo = Bingo::with_rules(vec![vec![1, 2, 3]], &[WinRule::Corners]);
+    mark_all(&mut bingo, &[1, 2]);
+    assert!(!bingo.has_won());
+    mark_all(&mut bingo, &[3]);
+    assert!(bingo.has_won());
+    assert_eq!(bingo.get_winning_line().unwrap().len(), 2);
+
+    let mut bingo = Bingo::with_rules(vec![vec![1], vec![2], vec![3]], &[WinRule::Corners]);
+    mark_all(&mut bingo, &[3]);
+    assert!(!bingo.has_won());
+    mark_all(&mut bingo, &[1]);
+    assert!(bingo.has_won());
+    assert_eq!(bingo.sum_of_unmarked_numbers(), 2);
+
+    let mut bingo = Bingo::with_rules(vec![vec![1]], &[WinRule::Corners]);
+    mark_all(&mut bingo, &[1]);
+    assert!(bingo.has_won());
+    assert_eq!(bingo.get_winning_line().unwrap().len(), 1);
 }
@@ -0,0 +1,149 @@
+use std::fmt;
+use crate::bingo::Bingo;
+
+/// a board winning, in the order the wins happened
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct WinEvent {
+    pub board_index: usize,
+    pub draw: u32,
+    pub draw_index: usize,
+    pub score: u32
+}
+
+/// winners count from 1, unlike boards
+#[derive(Eq, PartialEq, Debug)]
+pub enum WinnerError {
+    InvalidWinner(String),
+    NoWinnerZero
+}
+
+impl fmt::Display for WinnerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WinnerError::InvalidWinner(winner) => write!(f, "{:?} is not a number", winner),
+            WinnerError::NoWinnerZero => write!(f, "there is no winner 0, winners count from 1")
+        }
+    }
+}
+
+/// the winner given to --winner=k, counting from 1
+pub fn parse_winner(winner: &str) -> Result<usize, WinnerError> {
+    match winner.parse::<usize>() {
+        Ok(0) => Err(WinnerError::NoWinnerZero),
+        Ok(k) => Ok(k),
+        Err(_) => Err(WinnerError::InvalidWinner(winner.to_string()))
+    }
+}
+
+/// plays every draw against every board exactly once, recording each win as it happens
+///
+/// boards that have already won are no longer marked, so each board wins at most once and
+/// its score is fixed at the moment it wins. boards winning on the same draw are ordered by index
+pub struct BingoGame {
+    win_events: Vec<WinEvent>
+}
+
+impl BingoGame {
+    pub fn play(number_draws: &[u32], mut boards: Vec<Bingo>) -> BingoGame {
+        let mut win_events = vec![];
+
+        for (draw_index, draw) in number_draws.iter().copied().enumerate() {
+            if win_events.len() == boards.len() {
+                break;
+            }
+
+            for (board_index, board) in boards.iter_mut().enumerate() {
+                if board.has_won() {
+                    continue;
+                }
+
+                board.mark_number(draw);
+
+                if board.has_won() {
+                    let score = board.sum_of_unmarked_numbers() * draw;
+                    win_events.push(WinEvent { board_index, draw, draw_index, score });
+                }
+            }
+        }
+
+        BingoGame { win_events }
+    }
+
+    pub fn get_win_events(&self) -> &[WinEvent] {
+        &self.win_events
+    }
+
+    pub fn get_first_winner(&self) -> Option<&WinEvent> {
+        self.win_events.first()
+    }
+
+    /// the last board to win, which is only the last board overall if every board won
+    pub fn get_last_winner(&self) -> Option<&WinEvent> {
+        self.win_events.last()
+    }
+
+    /// the nth board to win, counting from 0
+    pub fn get_nth_winner(&self, n: usize) -> Option<&WinEvent> {
+        self.win_events.get(n)
+    }
+}
+
+#[cfg(test)]
+pub fn sample_game() -> (Vec<u32>, Vec<Bingo>) {
+    let number_draws = vec![7, 4, 9, 5, 11, 17, 23, 2, 0, 14, 21, 24, 10, 16, 13, 6, 15, 25, 12, 22, 18, 20, 8, 19, 3, 26, 1];
+
+    let boards = vec![
+        Bingo::new(vec![
+            vec![22, 13, 17, 11,  0],
+            vec![ 8,  2, 23,  4, 24],
+            vec![21,  9, 14, 16,  7],
+            vec![ 6, 10,  3, 18,  5],
+            vec![ 1, 12, 20, 15, 19]
+        ]),
+        Bingo::new(vec![
+            vec![ 3, 15,  0,  2, 22],
+            vec![ 9, 18, 13, 17,  5],
+            vec![19,  8,  7, 25, 23],
+            vec![20, 11, 10, 24,  4],
+            vec![14, 21, 16, 12,  6]
+        ]),
+        Bingo::new(vec![
+            vec![14, 21, 17, 24,  4],
+            vec![10, 16, 15,  9, 19],
+            vec![18,  8, 23, 26, 20],
+            vec![22, 11, 13,  6,  5],
+            vec![ 2,  0, 12,  3,  7]
+        ]),
+    ];
+
+    (number_draws, boards)
+}
+
+#[test]
+fn test_sample_input() {
+    let (number_draws, boards) = sample_game();
+    let game = BingoGame::play(&number_draws, boards);
+
+    assert_eq!(game.get_first_winner(), Some(&WinEvent { board_index: 2, draw: 24, draw_index: 11, score: 4512 }));
+    assert_eq!(game.get_nth_winner(1), Some(&WinEvent { board_index: 0, draw: 16, draw_index: 13, score: 2192 }));
+    assert_eq!(game.get_last_winner(), Some(&WinEvent { board_index: 1, draw: 13, draw_index: 14, score: 1924 }));
+    assert_eq!(game.get_nth_winner(3), None);
+    assert_eq!(game.get_win_events().len(), 3);
+}
+
+#[test]
+fn test_boards_that_never_win() {
+    let (number_draws, boards) = sample_game();
+    let game = BingoGame::play(&number_draws[0..12], boards);
+
+    assert_eq!(game.get_win_events().len(), 1);
+    assert_eq!(game.get_first_winner(), game.get_last_winner());
+}
+
+#[test]
+fn test_parse_winner() {
+    assert_eq!(parse_winner("2"), Ok(2));
+    assert_eq!(parse_winner("0"), Err(WinnerError::NoWinnerZero));
+    assert_eq!(parse_winner("x"), Err(WinnerError::InvalidWinner(String::from("x"))));
+    assert_eq!(parse_winner("-1").unwrap_err().to_string(), "\"-1\" is not a number");
+}
@@ -0,0 +1,124 @@
+use std::collections::HashSet;
+use std::fmt;
+use crate::bingo::{Bingo, WinRule};
+
+/// lines count from 1, boards count from 0 in the order they appear
+#[derive(Eq, PartialEq, Debug)]
+pub enum ParseError {
+    MissingDraws,
+    InvalidDraw(usize, String),
+    MissingBlankLine(usize),
+    NoBoards,
+    InvalidNumber(usize, usize, String),
+    InconsistentWidth(usize, usize, usize, usize),
+    DuplicateNumber(usize, usize, u32)
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::MissingDraws => write!(f, "the input has no draws"),
+            ParseError::InvalidDraw(line, draw) => write!(f, "line {}: invalid draw {:?}", line, draw),
+            ParseError::MissingBlankLine(line) => write!(f, "line {}: expected a blank line after the draws", line),
+            ParseError::NoBoards => write!(f, "the input has no boards"),
+            ParseError::InvalidNumber(board, line, number) => write!(f, "board {}, line {}: invalid number {:?}", board, line, number),
+            ParseError::InconsistentWidth(board, line, expected, found) => write!(f, "board {}, line {}: expected {} numbers, found {}", board, line, expected, found),
+            ParseError::DuplicateNumber(board, line, number) => write!(f, "board {}, line {}: {} is already on the board", board, line, number)
+        }
+    }
+}
+
+/// the comma separated draws on the first line, then boards separated by one or more blank lines
+pub fn parse_input(input: &str, rules: &[WinRule]) -> Result<(Vec<u32>, Vec<Bingo>), ParseError> {
+    let mut lines = input.lines().enumerate().map(|(index, line)| (index + 1, line.trim()));
+
+    let number_draws = match lines.next() {
+        Some((_, draws)) if !draws.is_empty() => parse_number_draws(draws)?,
+        _ => return Err(ParseError::MissingDraws)
+    };
+
+    if let Some((line, text)) = lines.next() {
+        if !text.is_empty() {
+            return Err(ParseError::MissingBlankLine(line));
+        }
+    }
+
+    let mut boards: Vec<Bingo> = vec![];
+    let mut board_data: Vec<Vec<u32>> = vec![];
+    let mut seen_numbers: HashSet<u32> = HashSet::new();
+
+    for (line, text) in lines {
+        if text.is_empty() {
+            if !board_data.is_empty() {
+                boards.push(Bingo::with_rules(board_data, rules));
+                board_data = vec![];
+                seen_numbers.clear();
+            }
+            continue;
+        }
+
+        let board_index = boards.len();
+
+        let board_row = text.split_whitespace()
+            .map(|value| value.parse::<u32>().map_err(|_| ParseError::InvalidNumber(board_index, line, value.to_string())))
+            .collect::<Result<Vec<u32>, ParseError>>()?;
+
+        if let Some(first_row) = board_data.first() {
+            if board_row.len() != first_row.len() {
+                return Err(ParseError::InconsistentWidth(board_index, line, first_row.len(), board_row.len()));
+            }
+        }
+
+        if let Some(number) = board_row.iter().find(|number| !seen_numbers.insert(**number)) {
+            return Err(ParseError::DuplicateNumber(board_index, line, *number));
+        }
+
+        board_data.push(board_row);
+    }
+
+    // the last board has no blank line after it
+    if !board_data.is_empty() {
+        boards.push(Bingo::with_rules(board_data, rules));
+    }
+
+    if boards.is_empty() {
+        return Err(ParseError::NoBoards);
+    }
+
+    Ok((number_draws, boards))
+}
+
+fn parse_number_draws(number_draws_csv: &str) -> Result<Vec<u32>, ParseError> {
+    number_draws_csv.split(',')
+        .map(|value| value.trim().parse::<u32>().map_err(|_| ParseError::InvalidDraw(1, value.to_string())))
+        .collect()
+}
+
+#[test]
+fn test_parse_input() {
+    let input = "7,4,9\r\n\r\n 1  2\r\n 3  4\r\n\r\n\r\n5 6 7\r\n";
+    let (number_draws, boards) = parse_input(input, &WinRule::standard()).unwrap();
+
+    assert_eq!(number_draws, vec![7, 4, 9]);
+    assert_eq!(boards.len(), 2);
+    assert_eq!(boards[0].get_dimensions(), (2, 2));
+    assert_eq!(boards[1].get_dimensions(), (1, 3));
+
+    // trimming the input used to drop the last board
+    let (_, boards) = parse_input(input.trim(), &WinRule::standard()).unwrap();
+    assert_eq!(boards.len(), 2);
+}
+
+#[test]
+fn test_parse_errors() {
+    let parse_error = |input: &str| parse_input(input, &WinRule::standard()).err().unwrap();
+
+    assert_eq!(parse_error(""), ParseError::MissingDraws);
+    assert_eq!(parse_error("1,x,3\n\n1 2"), ParseError::InvalidDraw(1, String::from("x")));
+    assert_eq!(parse_error("1,2\n3 4"), ParseError::MissingBlankLine(2));
+    assert_eq!(parse_error("1,2\n\n\n"), ParseError::NoBoards);
+    assert_eq!(parse_error("1,2\n\n1 2\n3 4\n\n5 6\n7 -8"), ParseError::InvalidNumber(1, 7, String::from("-8")));
+    assert_eq!(parse_error("1,2\n\n1 2\n3 4\n\n5 6\n7 8 9"), ParseError::InconsistentWidth(1, 7, 2, 3));
+    assert_eq!(parse_error("1,2\n\n1 2\n3 1"), ParseError::DuplicateNumber(0, 4, 1));
+    assert_eq!(parse_error("1,2\n\n1 1").to_string(), "board 0, line 3: 1 is already on the board");
+}
@@ -1,111 +1,94 @@
+mod analysis;
 mod bingo;
+mod game;
+mod input;
+mod replay;
 
-use bingo::Bingo;
-use std::fs;
-use std::collections::HashSet;
+use bingo::WinRule;
+use game::BingoGame;
+use std::{env, fs, io};
 
 fn main() {
     let input_file_contents = fs::read_to_string("inputs/input-04.txt").unwrap();
 
-    let input: Vec<&str> = input_file_contents
-        .trim()
-        .split("\r\n")
-        .collect();
+    // --rules=rows,columns,diagonals,corners,full picks how a board can win
+    let rules = env::args()
+        .find_map(|arg| arg.strip_prefix("--rules=").map(|rules| rules.split(',').map(WinRule::from_str).collect()))
+        .unwrap_or_else(WinRule::standard);
 
-    let number_draws = parse_number_draws(input[0]);
-    let bingo_boards = parse_boards(&input);
-
-    // since these functions both move & mutate the boards, can only call one
-    // println!("answer 4.1: {}", calculate_winning_board_and_score(number_draws, bingo_boards));
-    println!("answer 4.2: {}", calculate_last_winning_board_and_score(number_draws, bingo_boards));
-}
-
-fn calculate_winning_board_and_score(number_draws: Vec<u32>, mut bingo_boards: Vec<Bingo>) -> u32 {
-
-    let mut winning_board_index = None;
-    let mut winning_draw = None;
-    let board_count = bingo_boards.len();
-
-    'outer: for draw in number_draws.into_iter() {
+    let (number_draws, bingo_boards) = match input::parse_input(&input_file_contents, &rules) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            eprintln!("invalid input: {}", error);
+            std::process::exit(1);
+        }
+    };
+
+    // --analyze ranks the boards without playing, and checks the game agrees
+    let ranking = match env::args().any(|arg| arg == "--analyze") {
+        true => Some(analysis::rank_boards(&number_draws, &bingo_boards)),
+        false => None
+    };
+
+    // --replay renders every board after each draw, --replay=k only board k
+    let chosen_board = env::args().find_map(|arg| match arg.as_str() {
+        "--replay" => Some(Ok(None)),
+        _ => arg.strip_prefix("--replay=").map(|k| replay::parse_chosen_board(k, bingo_boards.len()).map(Some))
+    });
+
+    if let Some(chosen_board) = chosen_board {
+        let chosen_board = match chosen_board {
+            Ok(chosen_board) => chosen_board,
+            Err(error) => {
+                eprintln!("invalid replay: {}", error);
+                std::process::exit(1);
+            }
+        };
 
-        for board_index in 0..board_count {
-            let board = &mut bingo_boards[board_index];
+        replay::replay(&number_draws, bingo_boards.clone(), chosen_board, io::stdout().lock()).unwrap();
+    }
 
-            board.mark_number(draw);
+    let game = BingoGame::play(&number_draws, bingo_boards);
 
-            if board.has_won() {
-                winning_board_index = Some(board_index);
-                winning_draw = Some(draw);
-                break 'outer;
-            }
+    // the draws can run out, or the rules leave no way to win, before any board wins
+    match (game.get_first_winner(), game.get_last_winner()) {
+        (Some(first_winner), Some(last_winner)) => {
+            println!("answer 4.1: {}", first_winner.score);
+            println!("answer 4.2: {}", last_winner.score);
         }
+        _ => println!("no board won")
     }
 
-    bingo_boards[winning_board_index.unwrap()].sum_of_unmarked_numbers() * winning_draw.unwrap()
-}
-
-fn calculate_last_winning_board_and_score(number_draws: Vec<u32>, mut bingo_boards: Vec<Bingo>) -> u32 {
-
-    let mut winning_board_index_set:HashSet<usize> = HashSet::new();
-
-    let mut losing_board_index = None;
-    let mut losing_draw = None;
-
-    'outer: for draw in number_draws.into_iter() {
-
-        for board_index in 0..bingo_boards.len() {
-
-            match winning_board_index_set.get(&board_index) {
-                Some(_) => continue,
-                _ => ()
-            };
-
-            let board = &mut bingo_boards[board_index];
-
-            board.mark_number(draw);
-
-            if board.has_won() {
-                winning_board_index_set.insert(board_index);
+    if let Some(ranking) = ranking {
+        for (n, event) in ranking.iter().enumerate() {
+            println!("rank {}: board {} on draw {} ({}), score {}", n + 1, event.board_index, event.draw_index, event.draw, event.score);
+        }
 
-                if winning_board_index_set.len() == bingo_boards.len() {
-                    losing_board_index = Some(board_index);
-                    losing_draw = Some(draw);
-                    break 'outer;
-                }
-            }
+        match ranking == game.get_win_events() {
+            true => println!("analysis matches the game"),
+            false => println!("analysis differs from the game")
         }
     }
 
-    bingo_boards[losing_board_index.unwrap()].sum_of_unmarked_numbers() * losing_draw.unwrap()
-}
-
-fn parse_number_draws(number_draws_csv: &str) -> Vec<u32> {
-    number_draws_csv.split(",")
-        .map(|value| value.parse::<u32>().unwrap())
-        .collect()
-}
-
-fn parse_boards(input: &Vec<&str>) -> Vec<Bingo> {
-    let mut board_data: Vec<Vec<u32>> = vec![];
-    let mut boards: Vec<Bingo> = vec![];
-
-    for i in 1..input.len() {
-        let line = input[i];
-
-        if line == "" {
-            if board_data.len() == 5 {
-                boards.push(Bingo::new(board_data));
-                board_data = vec![];
-            }
-            continue;
+    if env::args().any(|arg| arg == "--wins") {
+        for (n, event) in game.get_win_events().iter().enumerate() {
+            println!("winner {}: board {} on draw {} ({}), score {}", n + 1, event.board_index, event.draw_index, event.draw, event.score);
         }
+    }
 
-        let board_row = line.split_whitespace()
-            .map(|value| value.parse::<u32>().unwrap())
-            .collect();
+    // --winner=k prints the kth board to win, counting from 1
+    if let Some(k) = env::args().find_map(|arg| arg.strip_prefix("--winner=").map(game::parse_winner)) {
+        let k = match k {
+            Ok(k) => k,
+            Err(error) => {
+                eprintln!("invalid winner: {}", error);
+                std::process::exit(1);
+            }
+        };
 
-        board_data.push(board_row);
+        match game.get_nth_winner(k - 1) {
+            Some(event) => println!("winner {}: board {}, score {}", k, event.board_index, event.score),
+            None => println!("winner {}: only {} boards won", k, game.get_win_events().len())
+        }
     }
-
-    boards
 }
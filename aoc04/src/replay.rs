@@ -0,0 +1,148 @@
+use std::fmt;
+use std::io::{self, Write};
+use crate::bingo::{Bingo, Position};
+
+const RESET: &str = "\x1b[0m";
+const MARKED: &str = "\x1b[1;32m";
+const WINNING_LINE: &str = "\x1b[1;30;43m";
+const LAST_DRAW: &str = "\x1b[4m";
+
+/// boards count from 0, like everywhere else in the game
+#[derive(Eq, PartialEq, Debug)]
+pub enum ReplayError {
+    InvalidBoard(String),
+    NoSuchBoard(usize, usize)
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::InvalidBoard(board) => write!(f, "invalid board {:?}", board),
+            ReplayError::NoSuchBoard(board, board_count) => write!(f, "there is no board {}, the boards are 0 to {}", board, board_count.saturating_sub(1))
+        }
+    }
+}
+
+/// the board given to --replay=k, checked against the boards in the game
+pub fn parse_chosen_board(board: &str, board_count: usize) -> Result<usize, ReplayError> {
+    let chosen_board = board.parse::<usize>().map_err(|_| ReplayError::InvalidBoard(board.to_string()))?;
+
+    match chosen_board < board_count {
+        true => Ok(chosen_board),
+        false => Err(ReplayError::NoSuchBoard(chosen_board, board_count))
+    }
+}
+
+/// draws a board with marked numbers in green, the number just drawn underlined
+/// and, once the board has won, its winning line picked out in yellow
+pub fn render_board(board: &Bingo, last_draw: Option<u32>) -> String {
+    let (height, width) = board.get_dimensions();
+    let winning_line = board.get_winning_line().unwrap_or(&[]);
+
+    let cell_width = (0..height)
+        .flat_map(|row| (0..width).map(move |col| Position { row, col }))
+        .map(|position| board.get_number(&position).to_string().len())
+        .max()
+        .unwrap_or(1);
+
+    let mut rendered = String::new();
+
+    for row in 0..height {
+        let cells: Vec<String> = (0..width)
+            .map(|col| {
+                let position = Position { row, col };
+                let number = board.get_number(&position);
+                let cell = format!("{:>width$}", number, width = cell_width);
+
+                let mut style = String::new();
+                if winning_line.iter().any(|winning| winning.row == row && winning.col == col) {
+                    style.push_str(WINNING_LINE);
+                } else if board.is_marked(&position) {
+                    style.push_str(MARKED);
+                }
+                if last_draw == Some(number) {
+                    style.push_str(LAST_DRAW);
+                }
+
+                match style.is_empty() {
+                    true => cell,
+                    false => format!("{}{}{}", style, cell, RESET)
+                }
+            })
+            .collect();
+
+        rendered.push_str(&cells.join(" "));
+        rendered.push('\n');
+    }
+
+    rendered
+}
+
+/// plays the game one draw at a time, rendering every board (or only the chosen one) after each draw
+/// and calling out each board as it wins. stops once every rendered board has won
+pub fn replay<W: Write>(number_draws: &[u32], mut boards: Vec<Bingo>, chosen_board: Option<usize>, mut writer: W) -> io::Result<()> {
+    assert!(chosen_board.is_none_or(|chosen| chosen < boards.len()), "the chosen board must be one of the boards");
+
+    let is_shown = |board_index: usize| chosen_board.is_none_or(|chosen| chosen == board_index);
+
+    for (draw_index, draw) in number_draws.iter().copied().enumerate() {
+        if boards.iter().enumerate().all(|(board_index, board)| !is_shown(board_index) || board.has_won()) {
+            break;
+        }
+
+        writeln!(writer, "draw {}: {}", draw_index, draw)?;
+
+        for (board_index, board) in boards.iter_mut().enumerate() {
+            if board.has_won() || !is_shown(board_index) {
+                continue;
+            }
+
+            board.mark_number(draw);
+
+            match board.has_won() {
+                true => writeln!(writer, "board {} wins, score {}", board_index, board.sum_of_unmarked_numbers() * draw)?,
+                false => writeln!(writer, "board {}", board_index)?
+            }
+
+            write!(writer, "{}", render_board(board, Some(draw)))?;
+        }
+
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_render_board() {
+    let mut board = Bingo::new(vec![vec![1, 2], vec![30, 4]]);
+
+    board.mark_number(2);
+    assert_eq!(render_board(&board, Some(2)), format!(" 1 {}{} 2{}\n30  4\n", MARKED, LAST_DRAW, RESET));
+
+    board.mark_number(4);
+    assert_eq!(render_board(&board, Some(4)), format!(" 1 {} 2{}\n30 {}{} 4{}\n", WINNING_LINE, RESET, WINNING_LINE, LAST_DRAW, RESET));
+}
+
+#[test]
+fn test_replay() {
+    let (number_draws, boards) = crate::game::sample_game();
+
+    let mut output = vec![];
+    replay(&number_draws, boards, Some(2), &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    // board 2 wins on the 12th draw, so the replay stops there
+    assert!(output.starts_with("draw 0: 7\nboard 2\n"));
+    assert!(output.contains("draw 11: 24\nboard 2 wins, score 4512\n"));
+    assert!(!output.contains("draw 12:"));
+    assert!(!output.contains("board 0"));
+}
+
+#[test]
+fn test_parse_chosen_board() {
+    assert_eq!(parse_chosen_board("2", 3), Ok(2));
+    assert_eq!(parse_chosen_board("3", 3), Err(ReplayError::NoSuchBoard(3, 3)));
+    assert_eq!(parse_chosen_board("x", 3), Err(ReplayError::InvalidBoard(String::from("x"))));
+    assert_eq!(parse_chosen_board("5", 3).unwrap_err().to_string(), "there is no board 5, the boards are 0 to 2");
+}
@@ -0,0 +1,229 @@
+use std::collections::{BTreeMap, HashSet};
+use crate::{Line, Mode, Point};
+
+/// the smallest step along a line, pointing right (or up, for vertical lines). every line lies on a
+/// carrier, the infinite line through it, identified by a key that is the same for all its points
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug)]
+struct Direction {
+    dx: i64,
+    dy: i64
+}
+
+/// the stretches of one carrier covered by at least one line, and by at least two, as sorted,
+/// disjoint, inclusive ranges of steps from the anchor, the start of the first line on the carrier.
+/// until it's swept, covered holds the range of each line as it was added
+struct Carrier {
+    anchor: Point,
+    covered: Vec<(i64, i64)>,
+    overlapped: Vec<(i64, i64)>
+}
+
+impl Direction {
+    fn of(line: &Line) -> Direction {
+        match line.get_step() {
+            (0, 0) => Direction { dx: 1, dy: 0 },
+            (dx, dy) if dx < 0 || (dx == 0 && dy < 0) => Direction { dx: -dx, dy: -dy },
+            (dx, dy) => Direction { dx, dy }
+        }
+    }
+
+    fn get_key(&self, point: Point) -> i128 {
+        self.dy as i128 * point.x as i128 - self.dx as i128 * point.y as i128
+    }
+}
+
+impl Carrier {
+    fn new(anchor: Point) -> Carrier {
+        Carrier { anchor, covered: vec![], overlapped: vec![] }
+    }
+
+    /// sweeps over the start and end of every line on the carrier, tracking how many lines are open
+    fn sweep(self) -> Carrier {
+        let mut events: Vec<(i64, i32)> = self.covered.iter()
+            .flat_map(|(start, end)| [(*start, 1), (end + 1, -1)])
+            .collect();
+        events.sort_unstable();
+
+        let mut carrier = Carrier::new(self.anchor);
+        let mut open_lines = 0;
+        let mut previous: Option<i64> = None;
+        let mut i = 0;
+
+        while i < events.len() {
+            let position = events[i].0;
+
+            if let Some(previous) = previous {
+                if open_lines >= 1 {
+                    push_range(&mut carrier.covered, (previous, position - 1));
+                }
+                if open_lines >= 2 {
+                    push_range(&mut carrier.overlapped, (previous, position - 1));
+                }
+            }
+
+            while i < events.len() && events[i].0 == position {
+                open_lines += events[i].1;
+                i += 1;
+            }
+
+            previous = Some(position);
+        }
+
+        carrier
+    }
+
+    /// how many steps of the direction a point on the carrier is from the anchor
+    fn get_position(&self, direction: &Direction, point: Point) -> i64 {
+        match direction.dx {
+            0 => (point.y - self.anchor.y) / direction.dy,
+            dx => (point.x - self.anchor.x) / dx
+        }
+    }
+
+    fn get_point(&self, direction: &Direction, position: i64) -> Point {
+        Point { x: self.anchor.x + position * direction.dx, y: self.anchor.y + position * direction.dy }
+    }
+}
+
+fn push_range(ranges: &mut Vec<(i64, i64)>, (start, end): (i64, i64)) {
+    match ranges.last_mut() {
+        Some(last) if last.1 + 1 == start => last.1 = end,
+        _ => ranges.push((start, end))
+    }
+}
+
+fn contains(ranges: &[(i64, i64)], position: i64) -> bool {
+    let i = ranges.partition_point(|(_, end)| *end < position);
+    i < ranges.len() && ranges[i].0 <= position
+}
+
+/// counts the points covered by two or more lines without visiting the points of each line,
+/// so the cost depends on the number of lines and crossings rather than on how long the lines are
+///
+/// lines on the same carrier are merged with a sweep, which gives their overlaps directly.
+/// the remaining points are where carriers of different directions cross
+pub fn count_overlapping_points(lines: &[Line], mode: Mode) -> u64 {
+    let mut unswept: BTreeMap<Direction, BTreeMap<i128, Carrier>> = BTreeMap::new();
+
+    for line in lines.iter().filter(|line| mode.includes(line)) {
+        let direction = Direction::of(line);
+
+        let carrier = unswept.entry(direction)
+            .or_default()
+            .entry(direction.get_key(line.start))
+            .or_insert_with(|| Carrier::new(line.start));
+
+        let (a, b) = (carrier.get_position(&direction, line.start), carrier.get_position(&direction, line.end));
+        carrier.covered.push((a.min(b), a.max(b)));
+    }
+
+    let carriers: Vec<(Direction, BTreeMap<i128, Carrier>)> = unswept.into_iter()
+        .map(|(direction, carriers)| (direction, carriers.into_iter().map(|(key, carrier)| (key, carrier.sweep())).collect()))
+        .collect();
+
+    let mut overlapping_points: i64 = carriers.iter()
+        .flat_map(|(_, carriers)| carriers.values())
+        .flat_map(|carrier| carrier.overlapped.iter())
+        .map(|(start, end)| end - start + 1)
+        .sum();
+
+    let mut crossings: HashSet<Point> = HashSet::new();
+
+    for (i, (a, a_carriers)) in carriers.iter().enumerate() {
+        for (b, b_carriers) in carriers[i + 1..].iter() {
+            // b's key changes by a fixed step for every step along a
+            let step = b.dy as i128 * a.dx as i128 - b.dx as i128 * a.dy as i128;
+
+            for a_carrier in a_carriers.values() {
+                for (start, end) in a_carrier.covered.iter() {
+                    let first_point = a_carrier.get_point(a, *start);
+                    let first = b.get_key(first_point);
+                    let last = first + step * (end - start) as i128;
+
+                    for (b_key, b_carrier) in b_carriers.range(first.min(last)..=first.max(last)) {
+                        if (b_key - first) % step != 0 {
+                            continue;
+                        }
+
+                        let point = a_carrier.get_point(a, start + ((b_key - first) / step) as i64);
+
+                        if contains(&b_carrier.covered, b_carrier.get_position(b, point)) {
+                            crossings.insert(point);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // a crossing is already counted once for every direction it overlaps in
+    for point in crossings {
+        let counted = carriers.iter()
+            .filter(|(direction, carriers)| {
+                carriers.get(&direction.get_key(point))
+                    .is_some_and(|carrier| contains(&carrier.overlapped, carrier.get_position(direction, point)))
+            })
+            .count() as i64;
+
+        overlapping_points += 1 - counted;
+    }
+
+    overlapping_points as u64
+}
+
+#[cfg(test)]
+fn line(x1: i64, y1: i64, x2: i64, y2: i64) -> Line {
+    Line { start: Point { x: x1, y: y1 }, end: Point { x: x2, y: y2 } }
+}
+
+#[test]
+fn test_long_lines() {
+    let lines = vec![
+        line(0, 0, 4_000_000_000, 0),
+        line(3_000_000_000, 0, 1_000_000_000, 0),
+        line(2_000_000_000, 4_000_000_000, 2_000_000_000, 0),
+        line(0, 4_000_000_000, 4_000_000_000, 0),
+        line(4_000_000_000, 4_000_000_000, 0, 0),
+    ];
+
+    // the stretch shared by the horizontal lines, which the vertical line crosses inside of,
+    // then the centre where three lines cross and the two corners the diagonals touch
+    assert_eq!(count_overlapping_points(&lines, Mode::Straight), 2_000_000_001);
+    assert_eq!(count_overlapping_points(&lines, Mode::Diagonal), 2_000_000_004);
+}
+
+#[test]
+fn test_diagonals_crossing_between_points() {
+    assert_eq!(count_overlapping_points(&[line(0, 0, 1, 1), line(1, 0, 0, 1)], Mode::Diagonal), 0);
+    assert_eq!(count_overlapping_points(&[line(0, 0, 2, 2), line(2, 0, 0, 2)], Mode::Diagonal), 1);
+}
+
+#[test]
+fn test_matches_rasterizing() {
+    let mut seed: u64 = 0x2545f4914f6cdd1d;
+    let mut next = |max: i64| {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % max as u64) as i64
+    };
+
+    for _ in 0..50 {
+        let lines: Vec<Line> = (0..40)
+            .map(|_| {
+                let (x, y, length) = (next(40) - 20, next(40) - 20, next(20));
+                match next(5) {
+                    0 => line(x, y, x + length, y),
+                    1 => line(x, y + length, x, y),
+                    2 => line(x + length, y + length, x, y),
+                    3 => line(x, y + length, x + length, y),
+                    _ => line(x, y, x - length, y + next(20) - 10)
+                }
+            })
+            .collect();
+
+        for mode in [Mode::Straight, Mode::Diagonal, Mode::Lattice] {
+            assert_eq!(count_overlapping_points(&lines, mode), crate::calculate_intersections(&lines, mode) as u64);
+        }
+    }
+}
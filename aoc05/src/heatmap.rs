@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+use crate::Point;
+
+/// the colour of no vents, then evenly spaced stops up to the most overlapping points
+const COLOR_SCALE: [[u8; 3]; 4] = [[0, 0, 0], [30, 40, 150], [0, 160, 170], [240, 220, 60]];
+const HIGHLIGHT: [u8; 3] = [255, 40, 40];
+
+/// coverage counts on a grid over the bounding box of every covered point. a map too large
+/// for the grid is scaled down, with each cell holding the highest count of the points it covers
+pub struct Heatmap {
+    width: usize,
+    height: usize,
+    counts: Vec<u32>,
+    max_count: u32
+}
+
+impl Heatmap {
+    pub fn new(coverage: &HashMap<Point, u32>, max_size: usize) -> Heatmap {
+        let (min_x, max_x) = coverage.keys().fold((i64::MAX, i64::MIN), |(min, max), point| (min.min(point.x), max.max(point.x)));
+        let (min_y, max_y) = coverage.keys().fold((i64::MAX, i64::MIN), |(min, max), point| (min.min(point.y), max.max(point.y)));
+
+        if coverage.is_empty() {
+            return Heatmap { width: 0, height: 0, counts: vec![], max_count: 0 };
+        }
+
+        let span = (max_x.abs_diff(min_x) + 1).max(max_y.abs_diff(min_y) + 1);
+        let scale = span.div_ceil(max_size.max(1) as u64);
+
+        let width = ((max_x.abs_diff(min_x) + 1).div_ceil(scale)) as usize;
+        let height = ((max_y.abs_diff(min_y) + 1).div_ceil(scale)) as usize;
+        let mut counts = vec![0; width * height];
+
+        for (point, count) in coverage {
+            let col = (point.x.abs_diff(min_x) / scale) as usize;
+            let row = (point.y.abs_diff(min_y) / scale) as usize;
+            counts[row * width + col] = counts[row * width + col].max(*count);
+        }
+
+        let max_count = counts.iter().copied().max().unwrap_or(0);
+
+        Heatmap { width, height, counts, max_count }
+    }
+
+    /// a binary greyscale image, brighter for more overlapping vents
+    pub fn write_pgm<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "P5\n{} {}\n255\n", self.width, self.height)?;
+
+        let pixels: Vec<u8> = self.counts.iter()
+            .map(|count| (*count as u64 * 255 / self.max_count.max(1) as u64) as u8)
+            .collect();
+
+        writer.write_all(&pixels)
+    }
+
+    /// a binary colour image on the colour scale, with points at or above the threshold highlighted
+    pub fn write_ppm<W: Write>(&self, threshold: u32, mut writer: W) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+
+        let pixels: Vec<u8> = self.counts.iter()
+            .flat_map(|count| self.get_color(*count, threshold))
+            .collect();
+
+        writer.write_all(&pixels)
+    }
+
+    /// one coloured cell per grid point using 24-bit ANSI background colours
+    pub fn render_preview(&self, threshold: u32) -> String {
+        let mut rendered = String::new();
+
+        for row in self.counts.chunks(self.width.max(1)) {
+            for count in row {
+                let [r, g, b] = self.get_color(*count, threshold);
+                rendered.push_str(&format!("\x1b[48;2;{};{};{}m  ", r, g, b));
+            }
+
+            rendered.push_str("\x1b[0m\n");
+        }
+
+        rendered
+    }
+
+    fn get_color(&self, count: u32, threshold: u32) -> [u8; 3] {
+        if count == 0 {
+            return COLOR_SCALE[0];
+        }
+        if count >= threshold {
+            return HIGHLIGHT;
+        }
+
+        // where the count falls between the first stop after no vents and the last
+        let stops = COLOR_SCALE.len() - 2;
+        let position = match self.max_count {
+            0 | 1 => 0.0,
+            max_count => (count - 1) as f64 / (max_count - 1) as f64 * stops as f64
+        };
+        let (lower, upper) = (position.floor() as usize, (position.ceil() as usize).min(stops));
+        let fraction = position - lower as f64;
+
+        let mut color = [0; 3];
+        for (channel, value) in color.iter_mut().enumerate() {
+            let (from, to) = (COLOR_SCALE[lower + 1][channel] as f64, COLOR_SCALE[upper + 1][channel] as f64);
+            *value = (from + (to - from) * fraction).round() as u8;
+        }
+
+        color
+    }
+}
+
+#[cfg(test)]
+fn coverage(points: &[(i64, i64, u32)]) -> HashMap<Point, u32> {
+    points.iter().map(|(x, y, count)| (Point { x: *x, y: *y }, *count)).collect()
+}
+
+#[test]
+fn test_write_images() {
+    let heatmap = Heatmap::new(&coverage(&[(-1, 5, 1), (1, 5, 3), (1, 6, 2)]), 100);
+
+    let mut pgm = vec![];
+    heatmap.write_pgm(&mut pgm).unwrap();
+    assert_eq!(pgm, [b"P5\n3 2\n255\n".as_slice(), &[85, 0, 255, 0, 0, 170]].concat());
+
+    let mut ppm = vec![];
+    heatmap.write_ppm(3, &mut ppm).unwrap();
+    assert_eq!(ppm, [b"P6\n3 2\n255\n".as_slice(), &[30, 40, 150, 0, 0, 0, 255, 40, 40], &[0, 0, 0, 0, 0, 0, 0, 160, 170]].concat());
+}
+
+#[test]
+fn test_scaled_down() {
+    let heatmap = Heatmap::new(&coverage(&[(0, 0, 1), (1, 1, 4), (3, 0, 2), (3, 3, 1)]), 2);
+
+    assert_eq!((heatmap.width, heatmap.height), (2, 2));
+    assert_eq!(heatmap.counts, vec![4, 2, 0, 1]);
+    assert_eq!(heatmap.render_preview(2).matches("\x1b[48;2;255;40;40m").count(), 2);
+    assert_eq!(heatmap.render_preview(2).lines().count(), 2);
+
+    let empty = Heatmap::new(&HashMap::new(), 2);
+    assert_eq!(empty.render_preview(2), "");
+}
@@ -1,28 +1,96 @@
-use std::fs;
+mod geometry;
+mod heatmap;
+mod vent_map;
+
+use std::{env, fs, io};
 use std::collections::HashMap;
+use vent_map::VentMap;
 
-#[derive(Hash, Eq, PartialEq, Debug)]
-struct Point { x: u32, y: u32 }
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
+struct Point { x: i64, y: i64 }
 struct Line { start: Point, end: Point }
 
+/// which lines count: the puzzle only has straight lines and 45 degree diagonals,
+/// lattice lines can be at any angle and cover every integer point along them
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+enum Mode {
+    Straight,
+    Diagonal,
+    Lattice
+}
+
 impl Point {
     fn from_str(string: &str) -> Point {
-        let coordinates: Vec<u32> = string
+        let coordinates: Vec<i64> = string
             .split(",")
-            .map(|coordinate| coordinate.parse::<u32>().unwrap())
+            .map(|coordinate| coordinate.trim().parse::<i64>().unwrap())
             .collect();
 
         Point { x: coordinates[0], y: coordinates[1] }
     }
 
     fn is_diagonal_to(&self, point: &Point) -> bool {
-        let x_diff = u32::max(self.x, point.x) - u32::min(self.x, point.x);
-        let y_diff = u32::max(self.y, point.y) - u32::min(self.y, point.y);
+        let x_diff = self.x.abs_diff(point.x);
+        let y_diff = self.y.abs_diff(point.y);
 
         x_diff == y_diff
     }
 }
 
+impl Line {
+    fn is_straight(&self) -> bool {
+        self.start.x == self.end.x || self.start.y == self.end.y
+    }
+
+    /// the smallest step from one integer point of the line to the next
+    fn get_step(&self) -> (i64, i64) {
+        let dx = self.end.x - self.start.x;
+        let dy = self.end.y - self.start.y;
+        let divisor = gcd(dx.unsigned_abs(), dy.unsigned_abs()).max(1) as i64;
+
+        (dx / divisor, dy / divisor)
+    }
+
+    /// every integer point on the line, from start to end
+    fn points(&self) -> impl Iterator<Item = Point> {
+        let (step_x, step_y) = self.get_step();
+        let steps = match (step_x, step_y) {
+            (0, 0) => 0,
+            (0, _) => (self.end.y - self.start.y) / step_y,
+            _ => (self.end.x - self.start.x) / step_x
+        };
+        let start = self.start;
+
+        (0..=steps).map(move |i| Point { x: start.x + i * step_x, y: start.y + i * step_y })
+    }
+}
+
+impl Mode {
+    fn from_str(s: &str) -> Mode {
+        match s {
+            "straight" => Mode::Straight,
+            "diagonal" => Mode::Diagonal,
+            "lattice" => Mode::Lattice,
+            _ => panic!("invalid mode")
+        }
+    }
+
+    fn includes(&self, line: &Line) -> bool {
+        match self {
+            Mode::Straight => line.is_straight(),
+            Mode::Diagonal => line.is_straight() || line.start.is_diagonal_to(&line.end),
+            Mode::Lattice => true
+        }
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b)
+    }
+}
+
 fn main() {
     let input_file_contents = fs::read_to_string("inputs/input-05.txt").unwrap();
 
@@ -39,11 +107,81 @@ fn main() {
         })
         .collect();
 
-    println!("answer 5.1: {}", calculate_intersections(&lines, true));
-    println!("answer 5.2: {}", calculate_intersections(&lines, false));
+    let rasterize = env::args().any(|arg| arg == "--rasterize");
+    let count_overlapping_points = |mode: Mode| match rasterize {
+        true => calculate_intersections(&lines, mode) as u64,
+        false => geometry::count_overlapping_points(&lines, mode)
+    };
+
+    // --mode=straight|diagonal|lattice counts for one kind of line, the default is the puzzle's two parts
+    // --rasterize counts by visiting every point of every line instead
+    match env::args().find_map(|arg| arg.strip_prefix("--mode=").map(Mode::from_str)) {
+        Some(mode) => println!("{:?}: {}", mode, count_overlapping_points(mode)),
+        None => {
+            println!("answer 5.1: {}", count_overlapping_points(Mode::Straight));
+            println!("answer 5.2: {}", count_overlapping_points(Mode::Diagonal));
+        }
+    }
+
+    let args: Vec<String> = env::args().collect();
+
+    if needs_vent_map(&args) {
+        run_vent_map_queries(&lines, &args);
+    }
+}
+
+/// the flags that ask about individual points, which need every point of every line visited
+const VENT_MAP_FLAGS: [&str; 6] = ["--at=", "--histogram", "--within=", "--pgm=", "--ppm=", "--preview"];
+
+/// only true when a flag needs a VentMap, so a plain run never rasterizes the lines
+fn needs_vent_map(args: &[String]) -> bool {
+    args.iter().any(|arg| VENT_MAP_FLAGS.iter().any(|flag| arg.starts_with(flag)))
+}
+
+fn run_vent_map_queries(lines: &[Line], args: &[String]) {
+    // points covered by --threshold=k or more lines (2 by default) are counted and highlighted below
+    let threshold = args.iter()
+        .find_map(|arg| arg.strip_prefix("--threshold=").map(|k| k.parse::<u32>().unwrap()))
+        .unwrap_or(2);
+
+    let vent_map_mode = args.iter().find_map(|arg| arg.strip_prefix("--mode=").map(Mode::from_str)).unwrap_or(Mode::Diagonal);
+    let mut vent_map = VentMap::new(lines, vent_map_mode);
+
+    // --within=x1,y1,x2,y2 narrows the map to a rectangle for everything below
+    if let Some(corners) = args.iter().find_map(|arg| arg.strip_prefix("--within=").map(String::from)) {
+        let coordinates: Vec<i64> = corners.split(',').map(|coordinate| coordinate.parse::<i64>().unwrap()).collect();
+        vent_map = vent_map.within(&Point { x: coordinates[0], y: coordinates[1] }, &Point { x: coordinates[2], y: coordinates[3] });
+    }
+
+    // --at=x,y prints how many lines cover a point
+    if let Some(point) = args.iter().find_map(|arg| arg.strip_prefix("--at=").map(Point::from_str)) {
+        println!("coverage at {},{}: {}", point.x, point.y, vent_map.get_coverage(&point));
+    }
+
+    if args.iter().any(|arg| arg == "--histogram") {
+        for (count, points) in vent_map.get_histogram() {
+            println!("{} lines: {} points", count, points);
+        }
+        println!("{} or more lines: {} points", threshold, vent_map.count_at_least(threshold));
+    }
+
+    // --pgm=path and --ppm=path write a heatmap of the map, --preview draws it in the terminal
+    if let Some(path) = args.iter().find_map(|arg| arg.strip_prefix("--pgm=").map(String::from)) {
+        let file = io::BufWriter::new(fs::File::create(path).unwrap());
+        heatmap::Heatmap::new(vent_map.get_counts(), 2000).write_pgm(file).unwrap();
+    }
+
+    if let Some(path) = args.iter().find_map(|arg| arg.strip_prefix("--ppm=").map(String::from)) {
+        let file = io::BufWriter::new(fs::File::create(path).unwrap());
+        heatmap::Heatmap::new(vent_map.get_counts(), 2000).write_ppm(threshold, file).unwrap();
+    }
+
+    if args.iter().any(|arg| arg == "--preview") {
+        print!("{}", heatmap::Heatmap::new(vent_map.get_counts(), 60).render_preview(threshold));
+    }
 }
 
-fn calculate_intersections(lines: &Vec<Line>, ignore_diagonal: bool) -> u32 {
+fn calculate_intersections(lines: &[Line], mode: Mode) -> u32 {
     let mut intersection_counts = 0;
     let mut coordinate_count_map: HashMap<Point, u32> = HashMap::new();
 
@@ -61,50 +199,9 @@ fn calculate_intersections(lines: &Vec<Line>, ignore_diagonal: bool) -> u32 {
         }
     };
 
-    for Line { start, end } in lines {
-        if start.x != end.x && start.y != end.y && ignore_diagonal {
-            continue;
-        }
-
-        if start.x == end.x {
-            let start_y = u32::min(start.y, end.y);
-            let end_y = u32::max(start.y, end.y);
-
-            for i in start_y..=end_y {
-                increment_coordinate_map(Point { x: start.x, y: i });
-            }
-            continue;
-        }
-
-        if start.y == end.y {
-            let start_x = u32::min(start.x, end.x);
-            let end_x = u32::max(start.x, end.x);
-
-            for i in start_x..=end_x {
-                increment_coordinate_map(Point { x: i, y: start.y });
-            }
-            continue;
-        }
-
-        if ignore_diagonal || !start.is_diagonal_to(end)  {
-            continue;
-        }
-
-        // handle diagonal
-        let x_diff = u32::max(start.x, end.x) - u32::min(start.x, end.x);
-
-        for offset in 0..=x_diff {
-            let x = match start.x < end.x {
-                true => start.x + offset,
-                false => start.x - offset
-            };
-
-            let y = match start.y < end.y {
-                true => start.y + offset,
-                false => start.y - offset
-            };
-
-            increment_coordinate_map(Point { x, y });
+    for line in lines.iter().filter(|line| mode.includes(line)) {
+        for point in line.points() {
+            increment_coordinate_map(point);
         }
     }
 
@@ -136,8 +233,10 @@ fn test_sample_input() {
         Line { start: Point { x: 5, y: 5 }, end: Point { x: 8, y: 2 } },
     ];
 
-    assert_eq!(calculate_intersections(&sample_input, true), 5);
-    assert_eq!(calculate_intersections(&sample_input, false), 12);
+    assert_eq!(calculate_intersections(&sample_input, Mode::Straight), 5);
+    assert_eq!(calculate_intersections(&sample_input, Mode::Diagonal), 12);
+    assert_eq!(geometry::count_overlapping_points(&sample_input, Mode::Straight), 5);
+    assert_eq!(geometry::count_overlapping_points(&sample_input, Mode::Diagonal), 12);
 }
 
 #[test]
@@ -154,3 +253,32 @@ fn test_point_is_diagonal_to() {
     assert!(!p2.is_diagonal_to(&p4));
     assert!(p3.is_diagonal_to(&p4));
 }
+
+#[test]
+fn test_plain_runs_never_rasterize() {
+    let args = |args: &[&str]| -> Vec<String> { args.iter().map(|arg| arg.to_string()).collect() };
+
+    assert!(!needs_vent_map(&args(&["aoc05"])));
+    assert!(!needs_vent_map(&args(&["aoc05", "--mode=lattice", "--threshold=3"])));
+    assert!(needs_vent_map(&args(&["aoc05", "--histogram"])));
+    assert!(needs_vent_map(&args(&["aoc05", "--ppm=vents.ppm"])));
+}
+
+#[test]
+fn test_lattice_lines() {
+    let line = Line { start: Point::from_str("6,3"), end: Point::from_str("-3,-3") };
+    let points: Vec<(i64, i64)> = line.points().map(|point| (point.x, point.y)).collect();
+
+    assert_eq!(points, vec![(6, 3), (3, 1), (0, -1), (-3, -3)]);
+
+    let lines = vec![
+        line,
+        Line { start: Point::from_str("3,-5"), end: Point::from_str("3,5") },
+        Line { start: Point::from_str("0,-1"), end: Point::from_str("0,-1") },
+        Line { start: Point::from_str("-1,0"), end: Point::from_str("1,-2") },
+    ];
+
+    assert_eq!(calculate_intersections(&lines, Mode::Straight), 0);
+    assert_eq!(calculate_intersections(&lines, Mode::Diagonal), 1);
+    assert_eq!(calculate_intersections(&lines, Mode::Lattice), 2);
+}
@@ -0,0 +1,83 @@
+use std::collections::{BTreeMap, HashMap};
+use crate::{Line, Mode, Point};
+
+/// how many lines cover each point
+pub struct VentMap {
+    counts: HashMap<Point, u32>
+}
+
+impl VentMap {
+    pub fn new(lines: &[Line], mode: Mode) -> VentMap {
+        let mut counts: HashMap<Point, u32> = HashMap::new();
+
+        for line in lines.iter().filter(|line| mode.includes(line)) {
+            for point in line.points() {
+                *counts.entry(point).or_insert(0) += 1;
+            }
+        }
+
+        VentMap { counts }
+    }
+
+    pub fn get_counts(&self) -> &HashMap<Point, u32> {
+        &self.counts
+    }
+
+    pub fn get_coverage(&self, point: &Point) -> u32 {
+        self.counts.get(point).copied().unwrap_or(0)
+    }
+
+    pub fn count_at_least(&self, k: u32) -> usize {
+        self.counts.values().filter(|count| **count >= k).count()
+    }
+
+    /// how many points are covered by each number of lines, leaving out points with no lines
+    pub fn get_histogram(&self) -> BTreeMap<u32, usize> {
+        let mut histogram = BTreeMap::new();
+
+        for count in self.counts.values() {
+            *histogram.entry(*count).or_insert(0) += 1;
+        }
+
+        histogram
+    }
+
+    /// the part of the map inside the rectangle with these opposite corners, edges included
+    pub fn within(&self, corner: &Point, opposite_corner: &Point) -> VentMap {
+        let (min_x, max_x) = (corner.x.min(opposite_corner.x), corner.x.max(opposite_corner.x));
+        let (min_y, max_y) = (corner.y.min(opposite_corner.y), corner.y.max(opposite_corner.y));
+
+        let counts = self.counts.iter()
+            .filter(|(point, _)| (min_x..=max_x).contains(&point.x) && (min_y..=max_y).contains(&point.y))
+            .map(|(point, count)| (*point, *count))
+            .collect();
+
+        VentMap { counts }
+    }
+}
+
+#[test]
+fn test_queries() {
+    let lines = vec![
+        Line { start: Point { x: 0, y: 0 }, end: Point { x: 3, y: 0 } },
+        Line { start: Point { x: 1, y: 0 }, end: Point { x: 1, y: 2 } },
+        Line { start: Point { x: 0, y: 2 }, end: Point { x: 2, y: 0 } },
+        Line { start: Point { x: 1, y: 1 }, end: Point { x: 1, y: 1 } },
+    ];
+
+    let vent_map = VentMap::new(&lines, Mode::Diagonal);
+
+    assert_eq!(vent_map.get_coverage(&Point { x: 1, y: 1 }), 3);
+    assert_eq!(vent_map.get_coverage(&Point { x: 5, y: 5 }), 0);
+    assert_eq!(vent_map.count_at_least(2), 3);
+    assert_eq!(vent_map.count_at_least(3), 1);
+    assert_eq!(vent_map.get_histogram(), BTreeMap::from([(1, 4), (2, 2), (3, 1)]));
+
+    let inside = vent_map.within(&Point { x: 3, y: 1 }, &Point { x: 1, y: 0 });
+    assert_eq!(inside.get_histogram(), BTreeMap::from([(1, 1), (2, 2), (3, 1)]));
+    assert_eq!(inside.get_coverage(&Point { x: 0, y: 0 }), 0);
+
+    let straight = VentMap::new(&lines, Mode::Straight);
+    assert_eq!(straight.get_histogram(), BTreeMap::from([(1, 4), (2, 2)]));
+    assert_eq!(straight.count_at_least(2) as u32, crate::calculate_intersections(&lines, Mode::Straight));
+}
@@ -0,0 +1,118 @@
+use std::fmt;
+
+/// an unsigned integer of any size, as base 2^32 limbs with the least significant first
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct BigUint {
+    limbs: Vec<u32>
+}
+
+impl BigUint {
+    pub fn from_u64(value: u64) -> BigUint {
+        let mut number = BigUint { limbs: vec![value as u32, (value >> 32) as u32] };
+        number.normalize();
+        number
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn add(&self, other: &BigUint) -> BigUint {
+        let length = self.limbs.len().max(other.limbs.len());
+        let mut limbs = Vec::with_capacity(length + 1);
+        let mut carry = 0u64;
+
+        for i in 0..length {
+            let sum = *self.limbs.get(i).unwrap_or(&0) as u64 + *other.limbs.get(i).unwrap_or(&0) as u64 + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+
+        limbs.push(carry as u32);
+
+        let mut number = BigUint { limbs };
+        number.normalize();
+        number
+    }
+
+    pub fn mul(&self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint { limbs: vec![] };
+        }
+
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+
+            for (j, b) in other.limbs.iter().enumerate() {
+                let product = *a as u64 * *b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = product as u32;
+                carry = product >> 32;
+            }
+
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+
+        let mut number = BigUint { limbs };
+        number.normalize();
+        number
+    }
+
+    /// divides in place, returning the remainder
+    fn div_rem_u32(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+
+        for limb in self.limbs.iter_mut().rev() {
+            let value = (remainder << 32) | *limb as u64;
+            *limb = (value / divisor as u64) as u32;
+            remainder = value % divisor as u64;
+        }
+
+        self.normalize();
+        remainder as u32
+    }
+
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        // nine decimal digits at a time, least significant first
+        let mut number = self.clone();
+        let mut chunks = vec![];
+
+        while !number.is_zero() {
+            chunks.push(number.div_rem_u32(1_000_000_000));
+        }
+
+        write!(f, "{}", chunks.last().unwrap())?;
+
+        for chunk in chunks.iter().rev().skip(1) {
+            write!(f, "{:09}", chunk)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_arithmetic() {
+    let a = BigUint::from_u64(u64::MAX);
+    let b = BigUint::from_u64(1);
+
+    assert_eq!(a.add(&b).to_string(), "18446744073709551616");
+    assert_eq!(a.mul(&a).to_string(), "340282366920938463426481119284349108225");
+    assert_eq!(a.mul(&BigUint::from_u64(0)), BigUint::from_u64(0));
+    assert_eq!(BigUint::from_u64(0).to_string(), "0");
+    assert_eq!(BigUint::from_u64(1_000_000_000).to_string(), "1000000000");
+    assert_eq!(BigUint::from_u64(1_000_000_007).mul(&BigUint::from_u64(1_000_000_000)).to_string(), "1000000007000000000");
+}
@@ -1,4 +1,11 @@
-use std::fs;
+mod big;
+mod matrix;
+mod population;
+mod timeline;
+
+use matrix::{Exact, Modular};
+use population::{Population, Species};
+use std::{env, fs, io};
 
 fn main() {
     let input_file_contents = fs::read_to_string("inputs/input-06.txt").unwrap();
@@ -10,11 +17,62 @@ fn main() {
         .collect();
 
     println!("answer 6.1: {}", calculate_reproduction(&timers, 80));
-    println!("answer 6.1: {}", calculate_reproduction(&timers, 256));
+    println!("answer 6.2: {}", calculate_reproduction(&timers, 256));
+
+    // --timeline=n writes every day's timers up to day n as csv, --growth=n how fast the fish multiply
+    if let Some(days) = env::args().find_map(|arg| arg.strip_prefix("--timeline=").map(|days| days.parse::<usize>().unwrap())) {
+        let histograms: Vec<[u64; 9]> = timeline::lanternfish(&timers).take(days + 1).collect();
+        timeline::write_csv(&histograms, io::stdout().lock()).unwrap();
+    }
+
+    if let Some(days) = env::args().find_map(|arg| arg.strip_prefix("--growth=").map(|days| days.parse::<usize>().unwrap())) {
+        let histograms: Vec<[u64; 9]> = timeline::lanternfish(&timers).take(days + 1).collect();
+        let stats = timeline::calculate_growth_stats(&histograms, 6);
+
+        for (day, ratio) in stats.ratios.iter().enumerate() {
+            println!("day {} to {}: {:.9}", day, day + 1, ratio);
+        }
+        println!("dominant eigenvalue: {:.9}", stats.dominant_eigenvalue);
+    }
+
+    // --species=cycle_length,newborn_delay,maturity[,lifespan] or --species=lanternfish adds a species starting from the input's timers,
+    // all of which are counted together after --days=n days (256 by default)
+    let species: Vec<Species> = env::args()
+        .filter_map(|arg| arg.strip_prefix("--species=").map(Species::from_str))
+        .collect();
+
+    if !species.is_empty() {
+        let days = env::args().find_map(|arg| arg.strip_prefix("--days=").map(|days| days.parse::<u32>().unwrap())).unwrap_or(256);
+        let mut population = Population::new();
+
+        for species in species {
+            population.add_species(species, &timers);
+        }
+
+        population.simulate(days);
+
+        for index in 0..population.get_species_count() {
+            println!("species {} after {} days: {}", index, days, population.get_species_total(index));
+        }
+        println!("all fish after {} days: {}", days, population.get_total());
+        return;
+    }
+
+    // --days=n counts the fish after any number of days, exactly or with --modulus=m modulo m
+    if let Some(days) = env::args().find_map(|arg| arg.strip_prefix("--days=").map(|days| days.parse::<u64>().unwrap())) {
+        match env::args().find_map(|arg| arg.strip_prefix("--modulus=").map(|modulus| modulus.parse::<u64>().unwrap())) {
+            Some(0) => {
+                eprintln!("invalid modulus: 0, the modulus must be at least 1");
+                std::process::exit(1);
+            }
+            Some(modulus) => println!("fish after {} days (mod {}): {}", days, modulus, matrix::count_fish(&Modular::new(modulus), &timers, days)),
+            None => println!("fish after {} days: {}", days, matrix::count_fish(&Exact, &timers, days))
+        }
+    }
 }
 
-fn calculate_reproduction(initial_timers: &Vec<usize>, days: u32) -> usize {
-    let mut timers = vec![0; 9];
+fn calculate_reproduction(initial_timers: &[usize], days: u32) -> usize {
+    let mut timers = [0; 9];
     let mut fish_total = initial_timers.len();
 
     for timer in initial_timers {
@@ -0,0 +1,180 @@
+use crate::big::BigUint;
+
+/// the numbers a population can be counted in
+pub trait Arithmetic {
+    type Value: Clone;
+
+    fn value(&self, value: u64) -> Self::Value;
+    fn add(&self, a: &Self::Value, b: &Self::Value) -> Self::Value;
+    fn mul(&self, a: &Self::Value, b: &Self::Value) -> Self::Value;
+}
+
+/// counts modulo some number, which stays cheap however many days pass
+pub struct Modular {
+    modulus: u64
+}
+
+impl Modular {
+    /// a modulus of 0 would divide by zero, so it's rejected here rather than on the first count
+    pub fn new(modulus: u64) -> Modular {
+        assert!(modulus > 0, "the modulus must be at least 1");
+        Modular { modulus }
+    }
+}
+
+/// counts exactly. the number of digits grows linearly with the days,
+/// so this is for days in the thousands to hundreds of thousands, not 10^12
+pub struct Exact;
+
+impl Arithmetic for Modular {
+    type Value = u64;
+
+    fn value(&self, value: u64) -> u64 {
+        value % self.modulus
+    }
+
+    fn add(&self, a: &u64, b: &u64) -> u64 {
+        ((*a as u128 + *b as u128) % self.modulus as u128) as u64
+    }
+
+    fn mul(&self, a: &u64, b: &u64) -> u64 {
+        ((*a as u128 * *b as u128) % self.modulus as u128) as u64
+    }
+}
+
+impl Arithmetic for Exact {
+    type Value = BigUint;
+
+    fn value(&self, value: u64) -> BigUint {
+        BigUint::from_u64(value)
+    }
+
+    fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a.add(b)
+    }
+
+    fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a.mul(b)
+    }
+}
+
+/// a square matrix
+#[derive(Clone, Debug)]
+pub struct Matrix<V> {
+    rows: Vec<Vec<V>>
+}
+
+impl<V: Clone> Matrix<V> {
+    pub fn from_u64<A: Arithmetic<Value = V>>(arithmetic: &A, rows: &[Vec<u64>]) -> Matrix<V> {
+        let rows = rows.iter()
+            .map(|row| row.iter().map(|value| arithmetic.value(*value)).collect())
+            .collect();
+
+        Matrix { rows }
+    }
+
+    pub fn multiply<A: Arithmetic<Value = V>>(&self, arithmetic: &A, other: &Matrix<V>) -> Matrix<V> {
+        let size = self.rows.len();
+
+        let rows = (0..size)
+            .map(|i| (0..size).map(|j| dot(arithmetic, &self.rows[i], (0..size).map(|k| &other.rows[k][j]))).collect())
+            .collect();
+
+        Matrix { rows }
+    }
+
+    /// the row vector times this matrix
+    pub fn multiply_row<A: Arithmetic<Value = V>>(&self, arithmetic: &A, row: &[V]) -> Vec<V> {
+        (0..self.rows.len())
+            .map(|j| dot(arithmetic, row, self.rows.iter().map(|matrix_row| &matrix_row[j])))
+            .collect()
+    }
+
+    /// the row vector times this matrix raised to a power, by repeated squaring
+    pub fn multiply_row_by_power<A: Arithmetic<Value = V>>(&self, arithmetic: &A, row: &[V], mut exponent: u64) -> Vec<V> {
+        let mut row = row.to_vec();
+        let mut square = self.clone();
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                row = square.multiply_row(arithmetic, &row);
+            }
+
+            exponent >>= 1;
+
+            if exponent > 0 {
+                square = square.multiply(arithmetic, &square);
+            }
+        }
+
+        row
+    }
+}
+
+fn dot<'a, A: Arithmetic>(arithmetic: &A, row: &'a [A::Value], column: impl Iterator<Item = &'a A::Value>) -> A::Value where A::Value: 'a {
+    row.iter()
+        .zip(column)
+        .fold(arithmetic.value(0), |sum, (a, b)| arithmetic.add(&sum, &arithmetic.mul(a, b)))
+}
+
+/// how a day changes the number of fish with each timer: row i says which of today's timers
+/// a fish with timer i becomes tomorrow. a fish at 0 becomes a 6 and a new 8
+pub fn transition_matrix() -> Vec<Vec<u64>> {
+    let mut matrix = vec![vec![0; 9]; 9];
+
+    for timer in 1..9 {
+        matrix[timer][timer - 1] = 1;
+    }
+
+    matrix[0][6] = 1;
+    matrix[0][8] = 1;
+
+    matrix
+}
+
+/// the number of fish after any number of days, in O(log days) matrix multiplications
+pub fn count_fish<A: Arithmetic>(arithmetic: &A, initial_timers: &[usize], days: u64) -> A::Value {
+    let mut counts = [0u64; 9];
+
+    for timer in initial_timers {
+        counts[*timer] += 1;
+    }
+
+    let row: Vec<A::Value> = counts.iter().map(|count| arithmetic.value(*count)).collect();
+    let matrix = Matrix::from_u64(arithmetic, &transition_matrix());
+
+    matrix.multiply_row_by_power(arithmetic, &row, days)
+        .iter()
+        .fold(arithmetic.value(0), |sum, count| arithmetic.add(&sum, count))
+}
+
+#[test]
+fn test_count_fish() {
+    let sample_input = vec![3, 4, 3, 1, 2];
+
+    assert_eq!(count_fish(&Modular::new(u64::MAX), &sample_input, 18), 26);
+    assert_eq!(count_fish(&Modular::new(u64::MAX), &sample_input, 256), 26984457539);
+    assert_eq!(count_fish(&Exact, &sample_input, 256).to_string(), "26984457539");
+    assert_eq!(count_fish(&Exact, &sample_input, 0).to_string(), "5");
+}
+
+#[test]
+fn test_exact_and_modular_agree() {
+    let sample_input = vec![3, 4, 3, 1, 2];
+    let modulus = 1_000_000_007;
+
+    let exact = count_fish(&Exact, &sample_input, 2000);
+    let reduced = exact.to_string().bytes().fold(0u64, |value, digit| (value * 10 + (digit - b'0') as u64) % modulus);
+
+    assert_eq!(count_fish(&Modular::new(modulus), &sample_input, 2000), reduced);
+    assert!(exact.to_string().len() > 70);
+
+    // far too many fish to count exactly, but cheap modulo a prime
+    assert!(count_fish(&Modular::new(modulus), &sample_input, 1_000_000_000_000) < modulus);
+}
+
+#[test]
+#[should_panic(expected = "the modulus must be at least 1")]
+fn test_zero_modulus() {
+    Modular::new(0);
+}
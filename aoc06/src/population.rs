@@ -0,0 +1,186 @@
+/// how one species of fish lives
+///
+/// a fish's timer counts down a day at a time. a fish at 0 resets to `cycle_length - 1` and, once it
+/// is `maturity` days old, spawns a newborn at `cycle_length - 1 + newborn_delay`. with a lifespan,
+/// fish die on the day they reach that age, after spawning
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct Species {
+    pub cycle_length: usize,
+    pub newborn_delay: usize,
+    pub maturity: usize,
+    pub lifespan: Option<usize>
+}
+
+/// fish of several species living side by side. each species counts its fish by age and timer,
+/// where fish older than the maturity are all the same age unless the species has a lifespan
+pub struct Population {
+    species: Vec<Species>,
+    counts: Vec<Vec<Vec<u64>>>
+}
+
+impl Species {
+    /// the puzzle's lanternfish
+    pub fn lanternfish() -> Species {
+        Species { cycle_length: 7, newborn_delay: 2, maturity: 0, lifespan: None }
+    }
+
+    /// cycle_length,newborn_delay,maturity and optionally a lifespan, or lanternfish
+    pub fn from_str(s: &str) -> Species {
+        if s == "lanternfish" {
+            return Species::lanternfish();
+        }
+
+        let parameters: Vec<usize> = s.split(',')
+            .map(|parameter| parameter.parse::<usize>().unwrap())
+            .collect();
+
+        match parameters.as_slice() {
+            [cycle_length, newborn_delay, maturity] => Species { cycle_length: *cycle_length, newborn_delay: *newborn_delay, maturity: *maturity, lifespan: None },
+            [cycle_length, newborn_delay, maturity, lifespan] => Species { cycle_length: *cycle_length, newborn_delay: *newborn_delay, maturity: *maturity, lifespan: Some(*lifespan) },
+            _ => panic!("invalid species")
+        }
+    }
+
+    fn get_newborn_timer(&self) -> usize {
+        self.cycle_length - 1 + self.newborn_delay
+    }
+
+    /// the oldest age the species keeps track of
+    fn get_oldest_age(&self) -> usize {
+        match self.lifespan {
+            Some(lifespan) => lifespan - 1,
+            None => self.maturity
+        }
+    }
+}
+
+impl Population {
+    pub fn new() -> Population {
+        Population { species: vec![], counts: vec![] }
+    }
+
+    /// adds fish of a new species, which start out just mature, returning the species' index.
+    /// initial timers can be longer than the species' own cycle, those fish just wait longer to spawn
+    pub fn add_species(&mut self, species: Species, initial_timers: &[usize]) -> usize {
+        assert!(species.cycle_length > 0, "a species needs a cycle of at least one day");
+        assert!(species.lifespan.is_none_or(|lifespan| lifespan > 0), "a species needs a lifespan of at least one day");
+
+        let longest_timer = initial_timers.iter()
+            .copied()
+            .chain([species.get_newborn_timer(), species.cycle_length - 1])
+            .max()
+            .unwrap();
+
+        let mut counts = vec![vec![0; longest_timer + 1]; species.get_oldest_age() + 1];
+        let age = species.maturity.min(species.get_oldest_age());
+
+        for timer in initial_timers {
+            counts[age][*timer] += 1;
+        }
+
+        self.species.push(species);
+        self.counts.push(counts);
+        self.species.len() - 1
+    }
+
+    pub fn simulate(&mut self, days: u32) {
+        for _ in 0..days {
+            self.step();
+        }
+    }
+
+    pub fn step(&mut self) {
+        for (species, counts) in self.species.iter().zip(self.counts.iter_mut()) {
+            let mut next = vec![vec![0; counts[0].len()]; counts.len()];
+
+            for (age, timers) in counts.iter().enumerate() {
+                let next_age = match species.lifespan {
+                    Some(lifespan) if age + 1 >= lifespan => None,
+                    Some(_) => Some(age + 1),
+                    None => Some((age + 1).min(species.maturity))
+                };
+
+                for (timer, count) in timers.iter().copied().enumerate() {
+                    if timer == 0 && age >= species.maturity {
+                        next[0][species.get_newborn_timer()] += count;
+                    }
+
+                    let next_timer = match timer {
+                        0 => species.cycle_length - 1,
+                        _ => timer - 1
+                    };
+
+                    if let Some(next_age) = next_age {
+                        next[next_age][next_timer] += count;
+                    }
+                }
+            }
+
+            *counts = next;
+        }
+    }
+
+    pub fn get_species_count(&self) -> usize {
+        self.species.len()
+    }
+
+    pub fn get_species_total(&self, species: usize) -> u64 {
+        self.counts[species].iter().flat_map(|timers| timers.iter()).sum()
+    }
+
+    pub fn get_total(&self) -> u64 {
+        (0..self.species.len()).map(|species| self.get_species_total(species)).sum()
+    }
+}
+
+#[cfg(test)]
+fn daily_totals(species: Species, initial_timers: &[usize], days: u32) -> Vec<u64> {
+    let mut population = Population::new();
+    population.add_species(species, initial_timers);
+
+    (0..days)
+        .map(|_| {
+            population.step();
+            population.get_total()
+        })
+        .collect()
+}
+
+#[test]
+fn test_lanternfish() {
+    let sample_input = vec![3, 4, 3, 1, 2];
+
+    for days in [18, 80, 256] {
+        let mut population = Population::new();
+        population.add_species(Species::lanternfish(), &sample_input);
+        population.simulate(days);
+
+        assert_eq!(population.get_total() as usize, crate::calculate_reproduction(&sample_input, days));
+    }
+}
+
+#[test]
+fn test_life_cycles() {
+    assert_eq!(daily_totals(Species::from_str("2,0,0"), &[0], 5), vec![2, 2, 4, 4, 8]);
+    assert_eq!(daily_totals(Species::from_str("2,0,2"), &[0], 5), vec![2, 2, 3, 3, 5]);
+    assert_eq!(daily_totals(Species::from_str("2,0,0,3"), &[0], 3), vec![2, 2, 3]);
+}
+
+#[test]
+fn test_timers_longer_than_the_cycle() {
+    assert_eq!(daily_totals(Species::from_str("3,0,0"), &[3, 4, 3, 1, 2], 5), vec![5, 6, 7, 9, 12]);
+    assert_eq!(daily_totals(Species::from_str("1,0,0"), &[2], 4), vec![1, 1, 2, 4]);
+}
+
+#[test]
+fn test_species_live_side_by_side() {
+    let mut population = Population::new();
+    let lanternfish = population.add_species(Species::lanternfish(), &[3, 4, 3, 1, 2]);
+    let mortal = population.add_species(Species::from_str("2,0,0,3"), &[0]);
+
+    population.simulate(3);
+
+    assert_eq!(population.get_species_total(lanternfish), 7);
+    assert_eq!(population.get_species_total(mortal), 3);
+    assert_eq!(population.get_total(), 10);
+}
@@ -0,0 +1,134 @@
+use std::io::{self, Write};
+
+/// the number of fish with each timer, one histogram per day starting with the initial fish.
+/// newborns start at N - 1 and parents reset to the reset timer. ends once the counts overflow a u64
+pub struct Timeline<const N: usize> {
+    histogram: Option<[u64; N]>,
+    reset_timer: usize
+}
+
+/// how fast the population grows from one day to the next
+pub struct GrowthStats {
+    pub ratios: Vec<f64>,
+    pub dominant_eigenvalue: f64
+}
+
+impl<const N: usize> Timeline<N> {
+    pub fn new(initial_timers: &[usize], reset_timer: usize) -> Timeline<N> {
+        assert!(reset_timer < N, "the reset timer must be a timer");
+
+        let mut histogram = [0; N];
+
+        for timer in initial_timers {
+            histogram[*timer] += 1;
+        }
+
+        Timeline { histogram: Some(histogram), reset_timer }
+    }
+}
+
+/// the puzzle's lanternfish
+pub fn lanternfish(initial_timers: &[usize]) -> Timeline<9> {
+    Timeline::new(initial_timers, 6)
+}
+
+impl<const N: usize> Iterator for Timeline<N> {
+    type Item = [u64; N];
+
+    fn next(&mut self) -> Option<[u64; N]> {
+        let histogram = self.histogram?;
+        let new_fish = histogram[0];
+
+        let mut next = [0; N];
+        next[..N - 1].copy_from_slice(&histogram[1..]);
+        next[N - 1] = new_fish;
+
+        self.histogram = next[self.reset_timer].checked_add(new_fish)
+            .map(|reset_count| {
+                next[self.reset_timer] = reset_count;
+                next
+            })
+            .filter(|next| next.iter().try_fold(0u64, |total, count| total.checked_add(*count)).is_some());
+
+        Some(histogram)
+    }
+}
+
+pub fn get_total<const N: usize>(histogram: &[u64; N]) -> u64 {
+    histogram.iter().sum()
+}
+
+/// writes one row per day: day,timer_0,...,timer_{N-1},total
+pub fn write_csv<const N: usize, W: Write>(histograms: &[[u64; N]], mut writer: W) -> io::Result<()> {
+    let timers: Vec<String> = (0..N).map(|timer| format!("timer_{}", timer)).collect();
+    writeln!(writer, "day,{},total", timers.join(","))?;
+
+    for (day, histogram) in histograms.iter().enumerate() {
+        let counts: Vec<String> = histogram.iter().map(|count| count.to_string()).collect();
+        writeln!(writer, "{},{},{}", day, counts.join(","), get_total(histogram))?;
+    }
+
+    Ok(())
+}
+
+/// the ratio of each day's total to the day before's, which tends to the dominant eigenvalue of the
+/// daily transition: the largest root of x^N = x^(N - reset_timer - 1) + 1
+pub fn calculate_growth_stats<const N: usize>(histograms: &[[u64; N]], reset_timer: usize) -> GrowthStats {
+    let ratios = histograms.windows(2)
+        .map(|days| get_total(&days[1]) as f64 / get_total(&days[0]) as f64)
+        .collect();
+
+    let polynomial = |x: f64| x.powi(N as i32) - x.powi((N - reset_timer - 1) as i32) - 1.0;
+
+    // the polynomial is negative at 1 and positive at 2, with only the one root between
+    let (mut low, mut high) = (1.0, 2.0);
+
+    for _ in 0..100 {
+        let middle = (low + high) / 2.0;
+
+        match polynomial(middle) < 0.0 {
+            true => low = middle,
+            false => high = middle
+        }
+    }
+
+    GrowthStats { ratios, dominant_eigenvalue: (low + high) / 2.0 }
+}
+
+#[test]
+fn test_timeline() {
+    let histograms: Vec<[u64; 9]> = lanternfish(&[3, 4, 3, 1, 2]).take(257).collect();
+
+    assert_eq!(histograms[0], [0, 1, 1, 2, 1, 0, 0, 0, 0]);
+    assert_eq!(histograms[2], [1, 2, 1, 0, 0, 0, 1, 0, 1]);
+    assert_eq!(get_total(&histograms[18]), 26);
+    assert_eq!(get_total(&histograms[256]), 26984457539);
+
+    // the counts stop fitting in a u64 a little before 500 days
+    let days = lanternfish(&[3, 4, 3, 1, 2]).count();
+    assert!(days > 400 && days < 500);
+}
+
+#[test]
+fn test_write_csv() {
+    let histograms: Vec<[u64; 9]> = lanternfish(&[0]).take(2).collect();
+
+    let mut csv = vec![];
+    write_csv(&histograms, &mut csv).unwrap();
+
+    assert_eq!(String::from_utf8(csv).unwrap(), "day,timer_0,timer_1,timer_2,timer_3,timer_4,timer_5,timer_6,timer_7,timer_8,total\n0,1,0,0,0,0,0,0,0,0,1\n1,0,0,0,0,0,0,1,0,1,2\n");
+}
+
+#[test]
+fn test_growth_converges() {
+    let histograms: Vec<[u64; 9]> = lanternfish(&[3, 4, 3, 1, 2]).collect();
+    let stats = calculate_growth_stats(&histograms, 6);
+
+    assert!((stats.dominant_eigenvalue.powi(9) - stats.dominant_eigenvalue.powi(2) - 1.0).abs() < 1e-12);
+    assert!((stats.dominant_eigenvalue - 1.091).abs() < 1e-3);
+    // the other eigenvalues are nearly as large, so the ratios settle slowly
+    let error = |ratio: &f64| (ratio - stats.dominant_eigenvalue).abs();
+    assert!(error(stats.ratios.last().unwrap()) < 1e-4);
+    assert!(error(stats.ratios.last().unwrap()) < error(&stats.ratios[100]) / 10.0);
+    assert_eq!(stats.ratios[0], 1.0);
+}
@@ -0,0 +1,133 @@
+/// the fuel a crab burns to move a distance. any implementation has to be convex and
+/// nondecreasing, which makes the total over all crabs convex in the target position
+pub trait FuelCost {
+    fn get_fuel(&self, distance: usize) -> usize;
+}
+
+/// a unit of fuel per step
+pub struct Linear;
+
+/// one more unit of fuel for each step than for the step before
+pub struct Triangular;
+
+/// where the crabs line up, and the fuel it takes them all to get there
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct Alignment {
+    pub position: usize,
+    pub fuel: usize
+}
+
+impl FuelCost for Linear {
+    fn get_fuel(&self, distance: usize) -> usize {
+        distance
+    }
+}
+
+impl FuelCost for Triangular {
+    fn get_fuel(&self, distance: usize) -> usize {
+        crate::get_non_constant_fuel_burned(distance)
+    }
+}
+
+pub fn calculate_total_fuel<C: FuelCost + ?Sized>(fuel_cost: &C, positions: &[usize], target: usize) -> usize {
+    positions.iter()
+        .map(|position| fuel_cost.get_fuel(position.abs_diff(target)))
+        .sum()
+}
+
+/// with linear fuel, any median is optimal
+pub fn align_linear(positions: &[usize]) -> Option<Alignment> {
+    let mut sorted = positions.to_vec();
+    let middle = sorted.len().checked_sub(1)? / 2;
+    let (_, median, _) = sorted.select_nth_unstable(middle);
+    let position = *median;
+
+    Some(Alignment { position, fuel: calculate_total_fuel(&Linear, positions, position) })
+}
+
+/// with triangular fuel, the optimum is within half a step of the mean, so one of the
+/// positions around it is optimal
+pub fn align_triangular(positions: &[usize]) -> Option<Alignment> {
+    let mean = positions.iter().sum::<usize>().checked_div(positions.len())?;
+    let (min, max) = get_range(positions)?;
+
+    (mean.saturating_sub(1)..=mean + 1)
+        .filter(|position| (min..=max).contains(position))
+        .map(|position| Alignment { position, fuel: calculate_total_fuel(&Triangular, positions, position) })
+        .min_by_key(|alignment| (alignment.fuel, alignment.position))
+}
+
+/// ternary search over the positions between the leftmost and rightmost crab, for any convex fuel cost.
+/// among equally good positions the leftmost is returned
+pub fn align<C: FuelCost + ?Sized>(fuel_cost: &C, positions: &[usize]) -> Option<Alignment> {
+    let (mut low, mut high) = get_range(positions)?;
+    let total_fuel = |target: usize| calculate_total_fuel(fuel_cost, positions, target);
+
+    while high - low > 2 {
+        let first_third = low + (high - low) / 3;
+        let second_third = high - (high - low) / 3;
+
+        // a convex function is only flat at its minimum, so on a tie the minimum is between the two
+        match total_fuel(first_third) <= total_fuel(second_third) {
+            true => high = second_third,
+            false => low = first_third + 1
+        }
+    }
+
+    (low..=high)
+        .map(|position| Alignment { position, fuel: total_fuel(position) })
+        .min_by_key(|alignment| (alignment.fuel, alignment.position))
+}
+
+fn get_range(positions: &[usize]) -> Option<(usize, usize)> {
+    Some((*positions.iter().min()?, *positions.iter().max()?))
+}
+
+#[cfg(test)]
+fn align_by_brute_force<C: FuelCost + ?Sized>(fuel_cost: &C, positions: &[usize]) -> usize {
+    let (min, max) = get_range(positions).unwrap();
+    (min..=max).map(|target| calculate_total_fuel(fuel_cost, positions, target)).min().unwrap()
+}
+
+#[test]
+fn test_sample_input() {
+    let sample_input = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+
+    assert_eq!(align_linear(&sample_input), Some(Alignment { position: 2, fuel: 37 }));
+    assert_eq!(align_triangular(&sample_input), Some(Alignment { position: 5, fuel: 168 }));
+    assert_eq!(align(&Linear, &sample_input), Some(Alignment { position: 2, fuel: 37 }));
+    assert_eq!(align(&Triangular, &sample_input), Some(Alignment { position: 5, fuel: 168 }));
+    assert_eq!(align_linear(&[]), None);
+    assert_eq!(align_triangular(&[]), None);
+    assert_eq!(align(&Linear, &[]), None);
+}
+
+#[test]
+fn test_matches_brute_force() {
+    struct Squared;
+
+    impl FuelCost for Squared {
+        fn get_fuel(&self, distance: usize) -> usize {
+            distance * distance
+        }
+    }
+
+    let mut seed: u64 = 0x9e3779b97f4a7c15;
+    let mut next = |max: u64| {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % max) as usize
+    };
+
+    for _ in 0..200 {
+        let crab_count = next(30) + 1;
+        let spread = next(2000) as u64 + 1;
+        let positions: Vec<usize> = (0..crab_count).map(|_| next(spread)).collect();
+
+        assert_eq!(align_linear(&positions).unwrap().fuel, align_by_brute_force(&Linear, &positions));
+        assert_eq!(align_triangular(&positions).unwrap().fuel, align_by_brute_force(&Triangular, &positions));
+        assert_eq!(align(&Squared, &positions).unwrap().fuel, align_by_brute_force(&Squared, &positions));
+        assert_eq!(align(&Linear, &positions).unwrap().fuel, align_by_brute_force(&Linear, &positions));
+    }
+}
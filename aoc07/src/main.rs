@@ -1,5 +1,8 @@
-use std::{fs, usize};
-use std::collections::HashMap;
+mod alignment;
+mod report;
+
+use alignment::{FuelCost, Linear, Triangular};
+use std::{env, fs, io};
 
 fn main() {
     let input_file_contents = fs::read_to_string("inputs/input-07.txt").unwrap();
@@ -10,51 +13,47 @@ fn main() {
         .map(|counter| counter.parse::<usize>().unwrap())
         .collect();
 
-    println!("answer 7.1: {}", calculate_most_fuel_efficient_lateral_movements(&positions, true));
-    println!("answer 7.2: {}", calculate_most_fuel_efficient_lateral_movements(&positions, false));
-}
-
-fn calculate_most_fuel_efficient_lateral_movements(positions: &Vec<usize>, constant_fuel_consumption: bool) -> usize {
-    let mut max = 0;
-    let mut min = usize::MAX;
-    let mut sum = 0;
-    let mut position_count_map: HashMap<usize, usize> = HashMap::new();
-
-    for position in positions {
-        max = max.max(*position);
-        min = min.min(*position);
-        sum += *position;
-
-        if position_count_map.contains_key(position) {
-            let new_count = position_count_map.get(position).unwrap() + 1;
-            position_count_map.insert(*position, new_count);
-        } else {
-            position_count_map.insert(*position, 1);
+    // --search finds the positions by ternary search, as it would for any other fuel cost
+    match env::args().any(|arg| arg == "--search") {
+        true => {
+            println!("answer 7.1: {}", alignment::align(&Linear, &positions).unwrap().fuel);
+            println!("answer 7.2: {}", alignment::align(&Triangular, &positions).unwrap().fuel);
+        },
+        false => {
+            println!("answer 7.1: {}", calculate_most_fuel_efficient_lateral_movements(&positions, true));
+            println!("answer 7.2: {}", calculate_most_fuel_efficient_lateral_movements(&positions, false));
         }
     }
 
-    // only check a max of 50% of the positions, centered on the average
-    let total_positions = positions.len();
-    let average = sum / positions.len();
-    let lower_bound = average.saturating_sub(total_positions / 4);
-    let upper_bound = usize::min(max, average + (total_positions / 4));
-
-    let mut fuel_consumption = vec![0usize; upper_bound - lower_bound];
+    // --report=linear|triangular prints the best positions, then the cost curve and each crab's fuel as CSV
+    if let Some(fuel_cost) = env::args().find_map(|arg| arg.strip_prefix("--report=").map(String::from)) {
+        match fuel_cost.as_str() {
+            "linear" => print_alignment_report(&Linear, &positions),
+            "triangular" => print_alignment_report(&Triangular, &positions),
+            _ => panic!("invalid fuel cost")
+        }
+    }
+}
 
-    for (position, count) in position_count_map.iter() {
-        for i in 0..fuel_consumption.len() {
-            let diff = usize::max(i, *position) - usize::min(i, *position);
+fn print_alignment_report(fuel_cost: &dyn FuelCost, positions: &[usize]) {
+    let report = report::create_alignment_report(fuel_cost, positions).unwrap();
 
-            fuel_consumption[i] += match constant_fuel_consumption {
-                true => diff * count,
-                false => get_non_constant_fuel_burned(diff) * count
-            }
+    println!("least fuel: {}", report.fuel);
+    println!("optimal positions: {:?}", report.optimal_positions);
+    println!();
 
+    report.write_cost_curve_csv(io::stdout()).unwrap();
+    println!();
+    report.write_crab_fuel_csv(io::stdout()).unwrap();
+}
 
-        }
-    }
+fn calculate_most_fuel_efficient_lateral_movements(positions: &[usize], constant_fuel_consumption: bool) -> usize {
+    let alignment = match constant_fuel_consumption {
+        true => alignment::align_linear(positions),
+        false => alignment::align_triangular(positions)
+    };
 
-    fuel_consumption.into_iter().min().unwrap()
+    alignment.unwrap().fuel
 }
 
 /// return factorial addition of distance
@@ -67,6 +66,7 @@ fn test_sample_input() {
     let sample_input = vec![16,1,2,0,4,2,7,1,2,14];
 
     assert_eq!(calculate_most_fuel_efficient_lateral_movements(&sample_input, true), 37);
+    assert_eq!(calculate_most_fuel_efficient_lateral_movements(&sample_input, false), 168);
 }
 
 #[test]
@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use crate::alignment::FuelCost;
+
+/// the fuel one crab burns to reach the chosen position
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct CrabFuel {
+    pub crab: usize,
+    pub position: usize,
+    pub fuel: usize
+}
+
+/// every position the crabs could line up at, and what it costs them
+pub struct AlignmentReport {
+    /// every position with the least total fuel, leftmost first
+    pub optimal_positions: Vec<usize>,
+    pub fuel: usize,
+    /// the total fuel for each position from the leftmost crab to the rightmost
+    pub first_position: usize,
+    pub cost_curve: Vec<usize>,
+    /// each crab's share of the fuel, in input order, when lining up at the leftmost optimal position
+    pub crab_fuel: Vec<CrabFuel>
+}
+
+impl AlignmentReport {
+    /// writes one row per position: position,fuel
+    pub fn write_cost_curve_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "position,fuel")?;
+
+        for (offset, fuel) in self.cost_curve.iter().enumerate() {
+            writeln!(writer, "{},{}", self.first_position + offset, fuel)?;
+        }
+
+        Ok(())
+    }
+
+    /// writes one row per crab: crab,position,fuel
+    pub fn write_crab_fuel_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "crab,position,fuel")?;
+
+        for CrabFuel { crab, position, fuel } in self.crab_fuel.iter() {
+            writeln!(writer, "{},{},{}", crab, position, fuel)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// works out the fuel for every position between the leftmost and rightmost crab, counting crabs
+/// that start at the same position together
+pub fn create_alignment_report<C: FuelCost + ?Sized>(fuel_cost: &C, positions: &[usize]) -> Option<AlignmentReport> {
+    let first_position = *positions.iter().min()?;
+    let last_position = *positions.iter().max()?;
+
+    let mut position_counts: BTreeMap<usize, usize> = BTreeMap::new();
+
+    for position in positions {
+        *position_counts.entry(*position).or_insert(0) += 1;
+    }
+
+    let cost_curve: Vec<usize> = (first_position..=last_position)
+        .map(|target| {
+            position_counts.iter()
+                .map(|(position, count)| fuel_cost.get_fuel(position.abs_diff(target)) * count)
+                .sum()
+        })
+        .collect();
+
+    let fuel = *cost_curve.iter().min()?;
+
+    let optimal_positions: Vec<usize> = cost_curve.iter()
+        .enumerate()
+        .filter(|(_, total)| **total == fuel)
+        .map(|(offset, _)| first_position + offset)
+        .collect();
+
+    let crab_fuel = positions.iter()
+        .enumerate()
+        .map(|(crab, position)| CrabFuel { crab, position: *position, fuel: fuel_cost.get_fuel(position.abs_diff(optimal_positions[0])) })
+        .collect();
+
+    Some(AlignmentReport { optimal_positions, fuel, first_position, cost_curve, crab_fuel })
+}
+
+#[test]
+fn test_sample_report() {
+    use crate::alignment::{Linear, Triangular};
+
+    let sample_input = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+
+    let report = create_alignment_report(&Linear, &sample_input).unwrap();
+    assert_eq!(report.optimal_positions, vec![2]);
+    assert_eq!(report.fuel, 37);
+    assert_eq!((report.first_position, report.cost_curve.len()), (0, 17));
+    assert_eq!(report.cost_curve[1], 41);
+    assert_eq!(report.cost_curve[3], 39);
+    assert_eq!(report.cost_curve[10], 71);
+    assert_eq!(report.crab_fuel[0], CrabFuel { crab: 0, position: 16, fuel: 14 });
+    assert_eq!(report.crab_fuel.iter().map(|crab| crab.fuel).sum::<usize>(), 37);
+
+    let report = create_alignment_report(&Triangular, &sample_input).unwrap();
+    assert_eq!(report.optimal_positions, vec![5]);
+    assert_eq!(report.fuel, 168);
+    assert_eq!(report.cost_curve[2], 206);
+    assert_eq!(report.crab_fuel[0].fuel, 66);
+}
+
+#[test]
+fn test_ties_and_csv() {
+    use crate::alignment::Linear;
+
+    let report = create_alignment_report(&Linear, &[3, 1, 3, 1]).unwrap();
+    assert_eq!(report.optimal_positions, vec![1, 2, 3]);
+
+    let mut curve = vec![];
+    report.write_cost_curve_csv(&mut curve).unwrap();
+    assert_eq!(String::from_utf8(curve).unwrap(), "position,fuel\n1,4\n2,4\n3,4\n");
+
+    let mut crabs = vec![];
+    report.write_crab_fuel_csv(&mut crabs).unwrap();
+    assert_eq!(String::from_utf8(crabs).unwrap(), "crab,position,fuel\n0,3,2\n1,1,0\n2,3,2\n3,1,0\n");
+
+    assert!(create_alignment_report(&Linear, &[]).is_none());
+}
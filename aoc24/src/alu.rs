@@ -0,0 +1,194 @@
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Register { W, X, Y, Z }
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Operand {
+    Register(Register),
+    Literal(i64)
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Instruction {
+    Inp(Register),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Div(Register, Operand),
+    Mod(Register, Operand),
+    Eql(Register, Operand)
+}
+
+#[derive(Eq, PartialEq, Debug)]
+pub enum AluError {
+    MissingInput(usize),
+    DivideByZero(usize),
+    InvalidModulo(usize),
+    Overflow(usize)
+}
+
+impl Register {
+    pub fn from_str(s: &str) -> Register {
+        match s {
+            "w" => Register::W,
+            "x" => Register::X,
+            "y" => Register::Y,
+            "z" => Register::Z,
+            _ => panic!("invalid register")
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        match self {
+            Register::W => 0,
+            Register::X => 1,
+            Register::Y => 2,
+            Register::Z => 3
+        }
+    }
+}
+
+impl Operand {
+    pub fn from_str(s: &str) -> Operand {
+        match s.parse::<i64>() {
+            Ok(value) => Operand::Literal(value),
+            Err(_) => Operand::Register(Register::from_str(s))
+        }
+    }
+}
+
+impl Instruction {
+    pub fn from_str(s: &str) -> Instruction {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+
+        if parts[0] == "inp" {
+            return Instruction::Inp(Register::from_str(parts[1]));
+        }
+
+        let a = Register::from_str(parts[1]);
+        let b = Operand::from_str(parts[2]);
+
+        match parts[0] {
+            "add" => Instruction::Add(a, b),
+            "mul" => Instruction::Mul(a, b),
+            "div" => Instruction::Div(a, b),
+            "mod" => Instruction::Mod(a, b),
+            "eql" => Instruction::Eql(a, b),
+            _ => panic!("invalid instruction")
+        }
+    }
+}
+
+pub struct Alu {
+    registers: [i64; 4]
+}
+
+impl Alu {
+    pub fn new() -> Alu {
+        Alu { registers: [0; 4] }
+    }
+
+    pub fn get(&self, register: Register) -> i64 {
+        self.registers[register.index()]
+    }
+
+    /// runs every instruction of the program, reading `inp` values from inputs in order
+    ///
+    /// errors carry the index of the instruction that could not be executed
+    pub fn run(&mut self, program: &[Instruction], inputs: &[i64]) -> Result<(), AluError> {
+        let mut inputs = inputs.iter();
+
+        for (index, instruction) in program.iter().enumerate() {
+            match *instruction {
+                Instruction::Inp(a) => match inputs.next() {
+                    Some(value) => self.set(a, *value),
+                    None => return Err(AluError::MissingInput(index))
+                },
+                Instruction::Add(a, b) => match self.get(a).checked_add(self.resolve(b)) {
+                    Some(value) => self.set(a, value),
+                    None => return Err(AluError::Overflow(index))
+                },
+                Instruction::Mul(a, b) => match self.get(a).checked_mul(self.resolve(b)) {
+                    Some(value) => self.set(a, value),
+                    None => return Err(AluError::Overflow(index))
+                },
+                Instruction::Div(a, b) => match self.resolve(b) {
+                    0 => return Err(AluError::DivideByZero(index)),
+                    // only i64::MIN / -1 is out of range
+                    b => match self.get(a).checked_div(b) {
+                        Some(value) => self.set(a, value),
+                        None => return Err(AluError::Overflow(index))
+                    }
+                },
+                Instruction::Mod(a, b) => match (self.get(a), self.resolve(b)) {
+                    (a, b) if a < 0 || b <= 0 => return Err(AluError::InvalidModulo(index)),
+                    (_, b) => self.set(a, self.get(a) % b)
+                },
+                Instruction::Eql(a, b) => self.set(a, match self.get(a) == self.resolve(b) { true => 1, false => 0 })
+            }
+        }
+
+        Ok(())
+    }
+
+    fn set(&mut self, register: Register, value: i64) {
+        self.registers[register.index()] = value;
+    }
+
+    fn resolve(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(register) => self.get(register),
+            Operand::Literal(value) => value
+        }
+    }
+}
+
+#[test]
+fn test_parse_instruction() {
+    assert_eq!(Instruction::from_str("inp w"), Instruction::Inp(Register::W));
+    assert_eq!(Instruction::from_str("add z y"), Instruction::Add(Register::Z, Operand::Register(Register::Y)));
+    assert_eq!(Instruction::from_str("div z -26"), Instruction::Div(Register::Z, Operand::Literal(-26)));
+}
+
+#[test]
+fn test_binary_conversion_program() {
+    let program: Vec<Instruction> = ["inp w", "add z w", "mod z 2", "div w 2", "add y w", "mod y 2", "div w 2", "add x w", "mod x 2", "div w 2", "mod w 2"]
+        .iter()
+        .map(|line| Instruction::from_str(line))
+        .collect();
+
+    let mut alu = Alu::new();
+    alu.run(&program, &[13]).unwrap();
+
+    assert_eq!(alu.get(Register::W), 1);
+    assert_eq!(alu.get(Register::X), 1);
+    assert_eq!(alu.get(Register::Y), 0);
+    assert_eq!(alu.get(Register::Z), 1);
+}
+
+#[test]
+fn test_runtime_errors() {
+    let divide = vec![Instruction::from_str("inp x"), Instruction::from_str("div y x")];
+    let modulo = vec![Instruction::from_str("inp x"), Instruction::from_str("mod x 3")];
+
+    assert_eq!(Alu::new().run(&divide, &[]), Err(AluError::MissingInput(0)));
+    assert_eq!(Alu::new().run(&divide, &[0]), Err(AluError::DivideByZero(1)));
+    assert_eq!(Alu::new().run(&modulo, &[-4]), Err(AluError::InvalidModulo(1)));
+}
+
+#[test]
+fn test_overflow() {
+    let square = |count: usize| -> Vec<Instruction> {
+        std::iter::once(Instruction::from_str("inp z"))
+            .chain(std::iter::repeat_n(Instruction::from_str("mul z z"), count))
+            .collect()
+    };
+    let add = vec![Instruction::from_str("inp z"), Instruction::from_str("add z z")];
+    let divide = vec![Instruction::from_str("inp z"), Instruction::from_str("div z -1")];
+
+    let mut alu = Alu::new();
+    alu.run(&square(5), &[3]).unwrap();
+    assert_eq!(alu.get(Register::Z), 3i64.pow(32));
+
+    assert_eq!(Alu::new().run(&square(6), &[3]), Err(AluError::Overflow(6)));
+    assert_eq!(Alu::new().run(&add, &[i64::MAX]), Err(AluError::Overflow(1)));
+    assert_eq!(Alu::new().run(&divide, &[i64::MIN]), Err(AluError::Overflow(1)));
+}
@@ -0,0 +1,182 @@
+use std::fmt;
+use crate::alu::{Instruction, Operand, Register};
+
+/// symbolic value of a register, built by running a program without knowing its inputs
+///
+/// `Input(n)` is the nth value read by `inp`, `Initial(r)` is the value register r held
+/// before the program started
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Expression {
+    Literal(i64),
+    Input(usize),
+    Initial(Register),
+    Add(Box<Expression>, Box<Expression>),
+    Mul(Box<Expression>, Box<Expression>),
+    Div(Box<Expression>, Box<Expression>),
+    Mod(Box<Expression>, Box<Expression>),
+    Eql(Box<Expression>, Box<Expression>)
+}
+
+impl Expression {
+    /// evaluates the expression for known inputs, which the tests check against the alu
+    #[cfg(test)]
+    pub fn get_value(&self, inputs: &[i64], initial: &[i64; 4]) -> i64 {
+        match self {
+            Expression::Literal(value) => *value,
+            Expression::Input(index) => inputs[*index],
+            Expression::Initial(register) => initial[register.index()],
+            Expression::Add(a, b) => a.get_value(inputs, initial) + b.get_value(inputs, initial),
+            Expression::Mul(a, b) => a.get_value(inputs, initial) * b.get_value(inputs, initial),
+            Expression::Div(a, b) => a.get_value(inputs, initial) / b.get_value(inputs, initial),
+            Expression::Mod(a, b) => a.get_value(inputs, initial) % b.get_value(inputs, initial),
+            Expression::Eql(a, b) => match a.get_value(inputs, initial) == b.get_value(inputs, initial) { true => 1, false => 0 },
+        }
+    }
+
+    /// inclusive bounds of every value this expression can take, assuming inputs are digits 1-9
+    pub fn get_range(&self) -> (i64, i64) {
+        match self {
+            Expression::Literal(value) => (*value, *value),
+            Expression::Input(_) => (1, 9),
+            Expression::Initial(_) => (i64::MIN, i64::MAX),
+            Expression::Add(a, b) => {
+                let (a, b) = (a.get_range(), b.get_range());
+                (a.0.saturating_add(b.0), a.1.saturating_add(b.1))
+            },
+            Expression::Mul(a, b) => {
+                let (a, b) = (a.get_range(), b.get_range());
+                let products = [a.0.saturating_mul(b.0), a.0.saturating_mul(b.1), a.1.saturating_mul(b.0), a.1.saturating_mul(b.1)];
+                (*products.iter().min().unwrap(), *products.iter().max().unwrap())
+            },
+            Expression::Div(a, b) => match (a.get_range(), b.as_ref()) {
+                ((low, high), Expression::Literal(divisor)) if *divisor > 0 => (low / divisor, high / divisor),
+                _ => (i64::MIN, i64::MAX)
+            },
+            Expression::Mod(_, b) => match b.as_ref() {
+                Expression::Literal(divisor) if *divisor > 0 => (0, divisor - 1),
+                _ => (0, i64::MAX)
+            },
+            Expression::Eql(_, _) => (0, 1)
+        }
+    }
+
+    fn add(a: Expression, b: Expression) -> Expression {
+        match (a, b) {
+            (Expression::Literal(a), Expression::Literal(b)) => Expression::Literal(a + b),
+            (Expression::Literal(0), e) | (e, Expression::Literal(0)) => e,
+            (a, b) => Expression::Add(Box::new(a), Box::new(b))
+        }
+    }
+
+    fn mul(a: Expression, b: Expression) -> Expression {
+        match (a, b) {
+            (Expression::Literal(a), Expression::Literal(b)) => Expression::Literal(a * b),
+            (Expression::Literal(0), _) | (_, Expression::Literal(0)) => Expression::Literal(0),
+            (Expression::Literal(1), e) | (e, Expression::Literal(1)) => e,
+            (a, b) => Expression::Mul(Box::new(a), Box::new(b))
+        }
+    }
+
+    fn div(a: Expression, b: Expression) -> Expression {
+        match (a, b) {
+            (Expression::Literal(a), Expression::Literal(b)) if b != 0 => Expression::Literal(a / b),
+            (e, Expression::Literal(1)) => e,
+            (a, b) => Expression::Div(Box::new(a), Box::new(b))
+        }
+    }
+
+    fn modulo(a: Expression, b: Expression) -> Expression {
+        match (a, b) {
+            (Expression::Literal(a), Expression::Literal(b)) if b > 0 => Expression::Literal(a % b),
+            (e, Expression::Literal(b)) if b > 0 && e.get_range().0 >= 0 && e.get_range().1 < b => e,
+            (a, b) => Expression::Mod(Box::new(a), Box::new(b))
+        }
+    }
+
+    fn eql(a: Expression, b: Expression) -> Expression {
+        let (a_low, a_high) = a.get_range();
+        let (b_low, b_high) = b.get_range();
+
+        match (a, b) {
+            (Expression::Literal(a), Expression::Literal(b)) => Expression::Literal(match a == b { true => 1, false => 0 }),
+            _ if a_high < b_low || b_high < a_low => Expression::Literal(0),
+            (a, b) => Expression::Eql(Box::new(a), Box::new(b))
+        }
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expression::Literal(value) => write!(f, "{}", value),
+            Expression::Input(index) => write!(f, "d{}", index),
+            Expression::Initial(register) => write!(f, "{}", format!("{:?}", register).to_lowercase()),
+            Expression::Add(a, b) => write!(f, "({} + {})", a, b),
+            Expression::Mul(a, b) => write!(f, "({} * {})", a, b),
+            Expression::Div(a, b) => write!(f, "({} / {})", a, b),
+            Expression::Mod(a, b) => write!(f, "({} % {})", a, b),
+            Expression::Eql(a, b) => write!(f, "({} == {})", a, b)
+        }
+    }
+}
+
+/// runs the program symbolically, numbering inputs from first_input
+///
+/// returns the final expression held by each register, in w, x, y, z order
+pub fn execute(program: &[Instruction], first_input: usize) -> [Expression; 4] {
+    let mut registers = [Register::W, Register::X, Register::Y, Register::Z].map(Expression::Initial);
+    let mut next_input = first_input;
+
+    for instruction in program {
+        let (a, value) = match *instruction {
+            Instruction::Inp(a) => {
+                next_input += 1;
+                (a, Expression::Input(next_input - 1))
+            },
+            Instruction::Add(a, b) => (a, Expression::add(registers[a.index()].clone(), resolve(&registers, b))),
+            Instruction::Mul(a, b) => (a, Expression::mul(registers[a.index()].clone(), resolve(&registers, b))),
+            Instruction::Div(a, b) => (a, Expression::div(registers[a.index()].clone(), resolve(&registers, b))),
+            Instruction::Mod(a, b) => (a, Expression::modulo(registers[a.index()].clone(), resolve(&registers, b))),
+            Instruction::Eql(a, b) => (a, Expression::eql(registers[a.index()].clone(), resolve(&registers, b)))
+        };
+
+        registers[a.index()] = value;
+    }
+
+    registers
+}
+
+fn resolve(registers: &[Expression; 4], operand: Operand) -> Expression {
+    match operand {
+        Operand::Register(register) => registers[register.index()].clone(),
+        Operand::Literal(value) => Expression::Literal(value)
+    }
+}
+
+#[test]
+fn test_push_block_simplifies() {
+    let block: Vec<Instruction> = crate::monad::Block { divisor: 1, x_offset: 12, y_offset: 4 }.instructions();
+    let [_, x, _, z] = execute(&block, 0);
+
+    assert_eq!(x, Expression::Literal(1));
+    assert_eq!(z.to_string(), "((z * 26) + (d0 + 4))");
+}
+
+#[test]
+fn test_symbolic_matches_alu() {
+    use crate::alu::Alu;
+
+    let block = crate::monad::Block { divisor: 26, x_offset: -7, y_offset: 3 }.instructions();
+    let [_, _, _, z] = execute(&block, 0);
+
+    for initial_z in [0, 5, 26 * 3 + 2, 26 * 11 + 9] {
+        for digit in 1..=9 {
+            let mut alu = Alu::new();
+            let mut program = vec![Instruction::Add(Register::Z, Operand::Literal(initial_z))];
+            program.extend(block.iter().copied());
+            alu.run(&program, &[digit]).unwrap();
+
+            assert_eq!(z.get_value(&[digit], &[0, 0, 0, initial_z]), alu.get(Register::Z));
+        }
+    }
+}
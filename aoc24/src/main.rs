@@ -0,0 +1,50 @@
+mod alu;
+mod expression;
+mod monad;
+
+use std::{env, fs};
+use alu::Instruction;
+use monad::Block;
+
+fn main() {
+    let input_file_contents = fs::read_to_string("inputs/input-24.txt").unwrap();
+
+    let program = parse(input_file_contents);
+
+    if env::args().any(|arg| arg == "--symbolic") {
+        print_symbolic_blocks(&program);
+    }
+
+    println!("answer 24.1: {}", to_model_number(&monad::find_model_number(&program, true).unwrap()));
+    println!("answer 24.2: {}", to_model_number(&monad::find_model_number(&program, false).unwrap()));
+}
+
+fn parse(input: String) -> Vec<Instruction> {
+    input.trim()
+        .split("\r\n")
+        .map(Instruction::from_str)
+        .collect()
+}
+
+fn to_model_number(digits: &[i64]) -> String {
+    digits.iter().map(|digit| digit.to_string()).collect()
+}
+
+/// prints the simplified value of z after each block, in terms of the z it started with
+fn print_symbolic_blocks(program: &[Instruction]) {
+    let blocks: Vec<Block> = monad::extract_blocks(program).unwrap();
+
+    for (digit, block) in blocks.iter().enumerate() {
+        let [_, _, _, z] = expression::execute(&block.instructions(), digit);
+        println!("{:>2}: z = {}", digit, z);
+    }
+}
+
+#[test]
+fn test_parse() {
+    let sample_input = String::from("inp w\r\nmul x 0\r\nadd x z\r\nmod x 26\r\ndiv z 1\r\nadd x 12\r\neql x w\r\neql x 0\r\nmul y 0\r\nadd y 25\r\nmul y x\r\nadd y 1\r\nmul z y\r\nmul y 0\r\nadd y w\r\nadd y 4\r\nmul y x\r\nadd z y");
+    let program = parse(sample_input);
+
+    assert_eq!(monad::extract_blocks(&program).unwrap(), vec![Block { divisor: 1, x_offset: 12, y_offset: 4 }]);
+    assert_eq!(to_model_number(&[1, 3, 9]), "139");
+}
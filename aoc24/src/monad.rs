@@ -0,0 +1,235 @@
+use crate::alu::{Alu, Instruction, Operand, Register};
+
+/// MONAD is made of 14 blocks of 18 instructions, one per digit, which only differ in three literals:
+///
+///     div z {divisor}     - 1 pushes the digit onto z (a base 26 stack), 26 pops the top entry
+///     add x {x_offset}    - a digit only avoids pushing if it equals the popped entry + x_offset
+///     add y {y_offset}    - the value pushed is digit + y_offset
+///
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct Block {
+    pub divisor: i64,
+    pub x_offset: i64,
+    pub y_offset: i64
+}
+
+/// digits[second] must equal digits[first] + offset for z to end at 0
+#[derive(Eq, PartialEq, Debug)]
+pub struct Constraint {
+    pub first: usize,
+    pub second: usize,
+    pub offset: i64
+}
+
+#[derive(Eq, PartialEq, Debug)]
+pub enum AnalysisError {
+    UnexpectedBlock(usize),
+    UnmatchedPop(usize),
+    UnmatchedPush(usize),
+    Unsatisfiable(usize, usize),
+    Rejected(Vec<i64>)
+}
+
+impl Block {
+    pub fn instructions(&self) -> Vec<Instruction> {
+        use Instruction::*;
+        use Operand::Literal;
+        use Register::{W, X, Y, Z};
+
+        vec![
+            Inp(W),
+            Mul(X, Literal(0)),
+            Add(X, Operand::Register(Z)),
+            Mod(X, Literal(26)),
+            Div(Z, Literal(self.divisor)),
+            Add(X, Literal(self.x_offset)),
+            Eql(X, Operand::Register(W)),
+            Eql(X, Literal(0)),
+            Mul(Y, Literal(0)),
+            Add(Y, Literal(25)),
+            Mul(Y, Operand::Register(X)),
+            Add(Y, Literal(1)),
+            Mul(Z, Operand::Register(Y)),
+            Mul(Y, Literal(0)),
+            Add(Y, Operand::Register(W)),
+            Add(Y, Literal(self.y_offset)),
+            Mul(Y, Operand::Register(X)),
+            Add(Z, Operand::Register(Y)),
+        ]
+    }
+}
+
+/// splits the program on each `inp` and reads the three literals out of every block,
+/// rejecting any block that doesn't have the MONAD shape
+pub fn extract_blocks(program: &[Instruction]) -> Result<Vec<Block>, AnalysisError> {
+    let mut blocks = vec![];
+    let mut block_start = 0;
+
+    for index in 1..=program.len() {
+        if index < program.len() && !matches!(program[index], Instruction::Inp(_)) {
+            continue;
+        }
+
+        let instructions = &program[block_start..index];
+
+        let block = match (instructions.get(4), instructions.get(5), instructions.get(15)) {
+            (Some(Instruction::Div(Register::Z, Operand::Literal(divisor))),
+             Some(Instruction::Add(Register::X, Operand::Literal(x_offset))),
+             Some(Instruction::Add(Register::Y, Operand::Literal(y_offset)))) => {
+                Block { divisor: *divisor, x_offset: *x_offset, y_offset: *y_offset }
+            },
+            _ => return Err(AnalysisError::UnexpectedBlock(blocks.len()))
+        };
+
+        if block.instructions() != instructions {
+            return Err(AnalysisError::UnexpectedBlock(blocks.len()));
+        }
+
+        blocks.push(block);
+        block_start = index;
+    }
+
+    Ok(blocks)
+}
+
+/// pairs every pushing block with the popping block that removes its value from z
+///
+/// a pushing block can never match its digit (x_offset is out of reach of a 1-9 digit), so
+/// the only way to empty z again is for every popping block to match the digit pushed
+pub fn find_constraints(blocks: &[Block]) -> Result<Vec<Constraint>, AnalysisError> {
+    let mut stack: Vec<(usize, i64)> = vec![];
+    let mut constraints = vec![];
+
+    for (digit, block) in blocks.iter().enumerate() {
+        match block.divisor {
+            // x is z % 26 + x_offset, somewhere in x_offset..=x_offset + 25
+            1 if block.x_offset > 9 || block.x_offset + 25 < 1 => stack.push((digit, block.y_offset)),
+            26 => match stack.pop() {
+                Some((first, y_offset)) => {
+                    let offset = y_offset + block.x_offset;
+
+                    if offset.abs() > 8 {
+                        return Err(AnalysisError::Unsatisfiable(first, digit));
+                    }
+
+                    constraints.push(Constraint { first, second: digit, offset });
+                },
+                None => return Err(AnalysisError::UnmatchedPop(digit))
+            },
+            _ => return Err(AnalysisError::UnexpectedBlock(digit))
+        }
+    }
+
+    match stack.pop() {
+        Some((digit, _)) => Err(AnalysisError::UnmatchedPush(digit)),
+        None => Ok(constraints)
+    }
+}
+
+/// picks each constrained pair of digits to be as large (or small) as possible
+pub fn solve_constraints(constraints: &[Constraint], digit_count: usize, largest: bool) -> Vec<i64> {
+    let mut digits = vec![0; digit_count];
+
+    for Constraint { first, second, offset } in constraints {
+        digits[*first] = match largest {
+            true => i64::min(9, 9 - offset),
+            false => i64::max(1, 1 - offset)
+        };
+        digits[*second] = digits[*first] + offset;
+    }
+
+    digits
+}
+
+/// finds the largest or smallest model number MONAD accepts, and double checks it on the ALU
+pub fn find_model_number(program: &[Instruction], largest: bool) -> Result<Vec<i64>, AnalysisError> {
+    let blocks = extract_blocks(program)?;
+    let constraints = find_constraints(&blocks)?;
+    let digits = solve_constraints(&constraints, blocks.len(), largest);
+
+    match is_valid_model_number(program, &digits) {
+        true => Ok(digits),
+        false => Err(AnalysisError::Rejected(digits))
+    }
+}
+
+pub fn is_valid_model_number(program: &[Instruction], digits: &[i64]) -> bool {
+    let mut alu = Alu::new();
+
+    match alu.run(program, digits) {
+        Ok(_) => alu.get(Register::Z) == 0,
+        Err(_) => false
+    }
+}
+
+#[cfg(test)]
+fn build_program(blocks: &[Block]) -> Vec<Instruction> {
+    blocks.iter().flat_map(|block| block.instructions()).collect()
+}
+
+#[test]
+fn test_extract_blocks() {
+    let blocks = vec![
+        Block { divisor: 1, x_offset: 13, y_offset: 6 },
+        Block { divisor: 26, x_offset: -2, y_offset: 11 },
+    ];
+
+    assert_eq!(extract_blocks(&build_program(&blocks)), Ok(blocks));
+
+    let mut program = build_program(&[Block { divisor: 1, x_offset: 13, y_offset: 6 }]);
+    program[9] = Instruction::Add(Register::Y, Operand::Literal(24));
+
+    assert_eq!(extract_blocks(&program), Err(AnalysisError::UnexpectedBlock(0)));
+}
+
+#[test]
+fn test_find_constraints() {
+    let blocks = vec![
+        Block { divisor: 1, x_offset: 11, y_offset: 3 },
+        Block { divisor: 1, x_offset: 14, y_offset: 7 },
+        Block { divisor: 26, x_offset: -5, y_offset: 1 },
+        Block { divisor: 26, x_offset: -8, y_offset: 9 },
+    ];
+
+    assert_eq!(find_constraints(&blocks), Ok(vec![
+        Constraint { first: 1, second: 2, offset: 2 },
+        Constraint { first: 0, second: 3, offset: -5 },
+    ]));
+
+    assert_eq!(find_constraints(&blocks[0..3]), Err(AnalysisError::UnmatchedPush(0)));
+    assert_eq!(find_constraints(&blocks[2..4]), Err(AnalysisError::UnmatchedPop(0)));
+
+    // with x_offset -25, x is at most 0 and can never match a digit either
+    let below_digits = vec![
+        Block { divisor: 1, x_offset: -25, y_offset: 4 },
+        Block { divisor: 26, x_offset: -2, y_offset: 9 },
+    ];
+    assert_eq!(find_constraints(&below_digits), Ok(vec![Constraint { first: 0, second: 1, offset: 2 }]));
+
+    let reachable = vec![Block { divisor: 1, x_offset: -24, y_offset: 4 }];
+    assert_eq!(find_constraints(&reachable), Err(AnalysisError::UnexpectedBlock(0)));
+}
+
+#[test]
+fn test_model_numbers_match_brute_force() {
+    let blocks = vec![
+        Block { divisor: 1, x_offset: 11, y_offset: 3 },
+        Block { divisor: 1, x_offset: 14, y_offset: 7 },
+        Block { divisor: 26, x_offset: -5, y_offset: 1 },
+        Block { divisor: 26, x_offset: -8, y_offset: 9 },
+    ];
+    let program = build_program(&blocks);
+
+    let mut accepted = vec![];
+
+    for n in 1111..=9999 {
+        let digits: Vec<i64> = n.to_string().chars().map(|c| c.to_digit(10).unwrap() as i64).collect();
+
+        if !digits.contains(&0) && is_valid_model_number(&program, &digits) {
+            accepted.push(digits);
+        }
+    }
+
+    assert_eq!(find_model_number(&program, true).unwrap(), *accepted.last().unwrap());
+    assert_eq!(find_model_number(&program, false).unwrap(), *accepted.first().unwrap());
+}
@@ -0,0 +1,121 @@
+use std::{env, fs};
+
+fn main() {
+    let input_file_contents = fs::read_to_string("inputs/input-25.txt").unwrap();
+
+    let matrix = parse(input_file_contents);
+    let render = env::args().any(|arg| arg == "--render");
+
+    println!("answer 25.1: {}", calculate_first_stationary_step(&matrix, render));
+}
+
+fn parse(input: String) -> Vec<Vec<char>> {
+    let mut matrix = vec![];
+
+    input.trim()
+        .split("\r\n")
+        .for_each(|string| {
+            matrix.push(string.chars().collect());
+        });
+
+    matrix
+}
+
+/// steps the herds until neither of them can move, returning the first step on which nothing moved
+///
+/// if render is set, every frame is printed as the herds move
+fn calculate_first_stationary_step(matrix: &[Vec<char>], render: bool) -> u32 {
+    let mut mutable_matrix = matrix.to_vec();
+    let mut step = 1;
+
+    if render {
+        print_matrix(&mutable_matrix, 0);
+    }
+
+    loop {
+        let moves = do_step(&mut mutable_matrix);
+
+        if render {
+            print_matrix(&mutable_matrix, step);
+        }
+
+        if moves == 0 {
+            break;
+        }
+
+        step += 1;
+    }
+
+    step
+}
+
+/// moves the east-facing herd, then the south-facing herd, returning the total number of sea cucumbers moved
+///
+/// every sea cucumber in a herd decides whether it can move before any of them moves,
+/// and both herds wrap around to the opposite edge of the grid
+fn do_step(matrix: &mut [Vec<char>]) -> u32 {
+    move_herd(matrix, '>', 0, 1) + move_herd(matrix, 'v', 1, 0)
+}
+
+fn move_herd(matrix: &mut [Vec<char>], herd: char, row_offset: usize, col_offset: usize) -> u32 {
+    let height = matrix.len();
+    let width = matrix.first().unwrap().len();
+    let mut moving_sea_cucumbers = vec![];
+
+    for (row, matrix_row) in matrix.iter().enumerate() {
+        for (col, cell) in matrix_row.iter().enumerate() {
+            if *cell != herd {
+                continue;
+            }
+
+            let next_row = (row + row_offset) % height;
+            let next_col = (col + col_offset) % width;
+
+            if matrix[next_row][next_col] == '.' {
+                moving_sea_cucumbers.push((row, col, next_row, next_col));
+            }
+        }
+    }
+
+    for (row, col, next_row, next_col) in moving_sea_cucumbers.iter() {
+        matrix[*row][*col] = '.';
+        matrix[*next_row][*next_col] = herd;
+    }
+
+    moving_sea_cucumbers.len() as u32
+}
+
+fn print_matrix(matrix: &[Vec<char>], step: u32) {
+    println!("\nstep {}:", step);
+    for row in matrix.iter() {
+        println!("{}", row.iter().collect::<String>());
+    }
+}
+
+#[test]
+fn test_single_row() {
+    let mut matrix = parse(String::from("...>>>>>..."));
+
+    assert_eq!(do_step(&mut matrix), 1);
+    assert_eq!(matrix[0].iter().collect::<String>(), "...>>>>.>..");
+    assert_eq!(do_step(&mut matrix), 2);
+    assert_eq!(matrix[0].iter().collect::<String>(), "...>>>.>.>.");
+}
+
+#[test]
+fn test_wrapping() {
+    let mut matrix = parse(String::from("...>...\r\n.......\r\n......>\r\nv.....>\r\n......>\r\n.......\r\n..vvv.."));
+
+    do_step(&mut matrix);
+
+    let expected = parse(String::from("..vv>..\r\n.......\r\n>......\r\nv.....>\r\n>......\r\n.......\r\n....v.."));
+    assert_eq!(matrix, expected);
+}
+
+#[test]
+fn test_sample_input() {
+    let sample_input = String::from("v...>>.vv>\r\n.vv>>.vv..\r\n>>.>v>...v\r\n>>v>>.>.v.\r\nv>v.vv.v..\r\n>.>>..v...\r\n.vv..>.>v.\r\nv.v..>>v.v\r\n....v..v.>");
+    let sample_matrix = parse(sample_input);
+
+    assert_eq!(calculate_first_stationary_step(&sample_matrix, false), 58);
+}